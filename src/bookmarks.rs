@@ -0,0 +1,121 @@
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+//! Per-file playback positions.
+//!
+//! Long files, like audiobooks or podcasts, are rarely listened to in one go.\
+//! When such a file is interrupted, its position is remembered, so that the next time its [`Track`] is played, playback resumes where it stopped.
+//!
+//! The positions are kept in a plain text file inside of the state directory, one `milliseconds<TAB>path` pair per line.\
+//! The paths are canonical, as produced by [`fmt_path`].
+//!
+//! [`Track`]: crate::playback::Track
+//! [`fmt_path`]: crate::utilities::fmt_path
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+use super::{utilities::state_path, Error};
+use std::{
+	cell::Cell,
+	collections::HashMap,
+	fs::{read_to_string, write},
+	io::ErrorKind,
+	path::{Path, PathBuf},
+	time::Duration,
+};
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// The name of the file, in the state directory, that holds the positions.
+const FILE_NAME: &str = "bookmarks";
+
+/// The minimum length a file must have for its position to be remembered.
+pub const THRESHOLD: Duration = Duration::from_secs(10 * 60);
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// A database of positions, keyed by canonical file path.
+pub struct Bookmarks {
+	/// Where the database is stored.
+	file_path: PathBuf,
+	positions: Cell<HashMap<PathBuf, Duration>>,
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+impl Bookmarks {
+	#[inline(always)]
+	/// Load the database from the state directory.
+	pub fn try_new() -> Result<Self, Error> {
+		Self::try_from_path(state_path(FILE_NAME)?)
+	}
+
+	/// Load the database from an arbitrary file.
+	///
+	/// A missing file is treated as an empty database, lines that cannot be parsed are ignored.
+	pub fn try_from_path(file_path: PathBuf) -> Result<Self, Error> {
+		let positions = match read_to_string(&file_path) {
+			Ok(contents) => contents
+				.lines()
+				.filter_map(|line| {
+					let (milliseconds, path) = line.split_once('\t')?;
+					let position = Duration::from_millis(
+						milliseconds
+							.parse()
+							.ok()?,
+					);
+					Some((PathBuf::from(path), position))
+				})
+				.collect(),
+			Err(error) if error.kind() == ErrorKind::NotFound => HashMap::new(),
+			Err(error) => Err(error)?,
+		};
+		Ok(Self {
+			file_path,
+			positions: Cell::new(positions),
+		})
+	}
+
+	#[inline]
+	/// Get the remembered position of a file.
+	pub fn position_get(&self, path: &Path) -> Option<Duration> {
+		let positions = self
+			.positions
+			.take();
+		let position = positions
+			.get(path)
+			.copied();
+		self.positions
+			.set(positions);
+		position
+	}
+
+	#[inline]
+	/// Remember the position of a file, and store the database.
+	pub fn position_set(&self, path: &Path, position: Duration) -> Result<(), Error> {
+		self.positions_update(|positions| {
+			positions.insert(PathBuf::from(path), position);
+		})
+	}
+
+	#[inline]
+	/// Forget the position of a file, and store the database.
+	///
+	/// Nothing is written if the file did not have a position.
+	pub fn position_unset(&self, path: &Path) -> Result<(), Error> {
+		if self
+			.position_get(path)
+			.is_none()
+		{
+			return Ok(());
+		}
+		self.positions_update(|positions| {
+			positions.remove(path);
+		})
+	}
+
+	/// Apply a change to the positions, and write them to the database file.
+	fn positions_update(&self, change: impl FnOnce(&mut HashMap<PathBuf, Duration>)) -> Result<(), Error> {
+		let mut positions = self
+			.positions
+			.take();
+		change(&mut positions);
+		let contents: String = positions
+			.iter()
+			.map(|(path, position)| format!("{}\t{}\n", position.as_millis(), path.display()))
+			.collect();
+		self.positions
+			.set(positions);
+		write(&self.file_path, contents).map_err(Error::from)
+	}
+}
@@ -0,0 +1,298 @@
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+//! Validation of playlist files, without playing them.
+//!
+//! Every file is parsed like it would be for playing, and every song is expanded through [`fmt_path`], and probed for being decodable.\
+//! Mistakes that keep a song from playing are errors; things that are most likely not meant (e.g.: unknown keys, or a song that is listed twice) are warnings.
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+use super::{
+	library::Query,
+	serde::{SerDePlaylist, SerDeTrack},
+	utilities::fmt_path,
+	Error,
+};
+use rodio::Decoder;
+use serde::{
+	de::{self, value, Visitor},
+	forward_to_deserialize_any, Deserialize, Deserializer,
+};
+use std::{
+	collections::HashSet,
+	fs::{read_to_string, File},
+	io::BufReader,
+	path::PathBuf,
+};
+use toml::{from_str, Table};
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[derive(Clone, Copy)]
+/// How bad a [`Finding`] is.
+pub enum Severity {
+	/// Something that keeps the playlist, or one of its songs, from playing.
+	Error,
+	/// Something that is most likely not meant.
+	Warning,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[derive(Clone)]
+/// A single mistake in a playlist file.
+pub struct Finding {
+	pub severity: Severity,
+	pub message: String,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[derive(Clone)]
+/// Every [`Finding`] of a single playlist file.
+pub struct Report {
+	/// The file, as it was given.
+	pub path: String,
+	pub findings: Vec<Finding>,
+}
+
+/// A [`Deserializer`] that only notes the keys of the structure that is deserialized from it, and then fails.
+struct KeysProbe<'a>(&'a mut &'static [&'static str]);
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// Get the keys that a structure is deserialized from, as its [`Deserialize`] implementation declares them.
+fn keys_get<'de, T: Deserialize<'de>>() -> &'static [&'static str] {
+	let mut keys: &'static [&'static str] = &[];
+	let _ = T::deserialize(KeysProbe(&mut keys));
+	keys
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+impl Report {
+	/// Check a playlist file.
+	pub fn check(path: &str) -> Self {
+		let mut report = Self {
+			path: String::from(path),
+			findings: Vec::new(),
+		};
+		let contents = match fmt_path(path).and_then(|expanded| read_to_string(expanded).map_err(Error::from)) {
+			Ok(contents) => contents,
+			Err(why) => {
+				report.error(format!("cannot be read; {why}"));
+				return report;
+			},
+		};
+		// NOTE: unknown keys are ignored when playing, so they are looked for in the plain table.
+		match from_str::<Table>(&contents) {
+			Ok(table) => report.keys_check(&table),
+			Err(why) => {
+				report.error(format!("cannot be parsed; '{why}'"));
+				return report;
+			},
+		}
+		match from_str::<SerDePlaylist>(&contents) {
+			Ok(list) => report.playlist_check(&list),
+			Err(why) => report.error(format!("is not a playlist; '{why}'")),
+		}
+		report
+	}
+
+	#[inline(always)]
+	/// Whether or not nothing was found.
+	pub fn is_clean(&self) -> bool {
+		self.findings
+			.is_empty()
+	}
+
+	#[inline]
+	/// Whether or not anything that keeps the playlist, or one of its songs, from playing was found.
+	pub fn has_errors(&self) -> bool {
+		self.findings
+			.iter()
+			.any(|finding| matches!(finding.severity, Severity::Error))
+	}
+
+	/// Look for keys that neither a playlist, nor its songs, have.
+	fn keys_check(&mut self, table: &Table) {
+		for key in table
+			.keys()
+			.filter(|key| !keys_get::<SerDePlaylist>().contains(&key.as_str()))
+		{
+			self.warning(format!("unknown key '{key}'"))
+		}
+		let Some(songs) = table
+			.get("song")
+			.and_then(|songs| songs.as_array())
+		else {
+			return;
+		};
+		for (index, song) in songs
+			.iter()
+			.enumerate()
+		{
+			for key in song
+				.as_table()
+				.into_iter()
+				.flat_map(|song| song.keys())
+				.filter(|key| !keys_get::<SerDeTrack>().contains(&key.as_str()))
+			{
+				self.warning(format!("unknown key '{key}' in song {}", index + 1))
+			}
+		}
+	}
+
+	/// Look for missing, undecodable and duplicate songs, and suspicious values.
+	fn playlist_check(&mut self, list: &SerDePlaylist) {
+		if list.song.is_empty() && list.query.is_none() && list.smart.is_none() {
+			self.error(String::from("has no songs, and neither a query, nor is smart"))
+		}
+		if let Some(Err(why)) = list
+			.query
+			.as_deref()
+			.map(Query::parse)
+		{
+			self.error(format!("invalid query; {why}"))
+		}
+		if list.time == Some(0) {
+			self.warning(String::from("'time = 0' plays once, like leaving it out (a negative time repeats forever)"))
+		}
+		if list.limit == Some(0) {
+			self.warning(String::from("'limit = 0' adds no songs"))
+		}
+		if list.limit.is_some() && list.query.is_none() && list.smart.is_none() {
+			self.warning(String::from("'limit' has no effect without a query, or being smart"))
+		}
+		if list.radio.is_some() && list.time.is_some() {
+			self.warning(String::from("'time' has no effect on a radio, which plays until it is skipped"))
+		}
+
+		let mut seen: HashSet<PathBuf> = HashSet::new();
+		for (index, song) in list
+			.song
+			.iter()
+			.enumerate()
+		{
+			let number = index + 1;
+			let file = &song.file;
+			if song.time == Some(0) {
+				self.warning(format!("'time = 0' of song {number} ('{file}') plays once, like leaving it out"))
+			}
+			if song.weight == Some(0) {
+				self.warning(format!("'weight = 0' of song {number} ('{file}') always plays it last in the weighted shuffle"))
+			}
+			let path = match fmt_path(file) {
+				Ok(path) => path,
+				Err(why) => {
+					self.error(format!("song {number} ('{file}') cannot be found; {why}"));
+					continue;
+				},
+			};
+			if !seen.insert(path.clone()) {
+				self.warning(format!("song {number} ('{file}') is listed more than once"));
+				continue;
+			}
+			let decoded = File::open(&path)
+				.map_err(Error::from)
+				.and_then(|opened| Decoder::new(BufReader::new(opened)).map_err(Error::from));
+			if let Err(why) = decoded {
+				self.error(format!("song {number} ('{file}') cannot be played; {why}"))
+			}
+		}
+	}
+
+	#[inline(always)]
+	fn error(&mut self, message: String) {
+		self.findings
+			.push(Finding {
+				severity: Severity::Error,
+				message,
+			})
+	}
+
+	#[inline(always)]
+	fn warning(&mut self, message: String) {
+		self.findings
+			.push(Finding {
+				severity: Severity::Warning,
+				message,
+			})
+	}
+}
+
+impl Severity {
+	#[inline]
+	/// Get the name of `self`, as it is printed in a [`Report`].
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::Error => "error",
+			Self::Warning => "warning",
+		}
+	}
+}
+
+impl<'de> Deserializer<'de> for KeysProbe<'_> {
+	type Error = value::Error;
+
+	fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+		Err(de::Error::custom("not a structure"))
+	}
+
+	fn deserialize_struct<V: Visitor<'de>>(self, _: &'static str, fields: &'static [&'static str], _: V) -> Result<V::Value, Self::Error> {
+		*self.0 = fields;
+		Err(de::Error::custom("probed"))
+	}
+
+	forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option unit unit_struct
+		newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+	}
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::{
+		env::temp_dir,
+		fs::{create_dir_all, remove_dir_all, write},
+		process,
+	};
+
+	#[test]
+	fn keys_of_playlists() {
+		assert_eq!(
+			keys_get::<SerDePlaylist>(),
+			["song", "query", "smart", "limit", "time", "vary", "seed", "radio", "sort", "filter"]
+		);
+	}
+
+	#[test]
+	fn keys_of_songs() {
+		assert_eq!(keys_get::<SerDeTrack>(), ["file", "time", "weight", "title", "artist", "album"]);
+	}
+
+	#[test]
+	fn report_findings() {
+		let directory = temp_dir().join(format!("quing-check-{}", process::id()));
+		create_dir_all(&directory).unwrap();
+		let song = directory.join("song.flac");
+		write(&song, "").unwrap();
+		let playlist = directory.join("playlist.toml");
+		let contents = format!(
+			"time = 0\ncolour = \"red\"\n[[song]]\nfile = {0:?}\n[[song]]\nfile = {0:?}\nmood = \"calm\"\n",
+			song.display()
+		);
+		write(&playlist, contents).unwrap();
+		let report = Report::check(&playlist.to_string_lossy());
+		let warnings: Vec<&str> = report
+			.findings
+			.iter()
+			.filter(|finding| matches!(finding.severity, Severity::Warning))
+			.map(|finding| finding.message.as_str())
+			.collect();
+		assert!(warnings.contains(&"unknown key 'colour'"));
+		assert!(warnings.contains(&"unknown key 'mood' in song 2"));
+		assert!(warnings.iter().any(|warning| warning.starts_with("'time = 0'")));
+		assert!(warnings.iter().any(|warning| warning.starts_with("song 2") && warning.ends_with("is listed more than once")));
+		// NOTE: the empty file cannot be decoded.
+		assert!(report.has_errors());
+		let _ = remove_dir_all(&directory);
+	}
+
+	#[test]
+	fn report_unreadable() {
+		let report = Report::check("/nowhere/playlist.toml");
+		assert!(report.has_errors() && report.findings.len() == 1);
+	}
+}
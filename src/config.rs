@@ -0,0 +1,506 @@
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+//! The global configuration file.
+//!
+//! The file lies at `$XDG_CONFIG_HOME/quing/config.toml` (`~/.config/quing/config.toml` by default), and is entirely optional.\
+//! It is validated as a whole when it is loaded, so that mistakes surface at start-up, and not in the middle of playback.
+//!
+//! # Example:
+//!
+//! ```toml
+//! flags = "f"
+//! volume = 0.8
+//! step = 0.05
+//! shuffle = "album"
+//! library = ["~/Music"]
+//! status = "{state} {track} [{position}] {volume}"
+//! on_error = "prompt"
+//!
+//! [keys]
+//! space = "play"
+//! right = "track-next"
+//! left = "track-back"
+//! "ctrl+q" = "exit"
+//! ```
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+use super::{
+	in_out::Signal,
+	playback::ErrorPolicy,
+	serde::SerDeVary,
+	utilities::{config_path, fmt_path},
+	ConfigError, Error,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use serde::Deserialize;
+use std::{
+	collections::{BTreeMap, HashMap},
+	fs::read_to_string,
+	io::ErrorKind,
+	path::{Path, PathBuf},
+};
+use toml::from_str;
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// The name of the configuration file, in the configuration directory.
+const FILE_NAME: &str = "config.toml";
+
+/// The bindings that are in place, unless they are overridden.
+const DEFAULT_BINDINGS: [(&str, Signal); 26] = [
+	("ctrl+l", Signal::PlaylistNext),
+	("ctrl+j", Signal::PlaylistBack),
+	("ctrl+k", Signal::Exit),
+	("ctrl+h", Signal::PlaylistReset),
+	("l", Signal::TrackNext),
+	("j", Signal::TrackBack),
+	("k", Signal::Play),
+	("h", Signal::TrackReset),
+	("L", Signal::VolumeIncrease),
+	("J", Signal::VolumeDecrease),
+	("K", Signal::Mute),
+	("H", Signal::VolumeReset),
+	("r", Signal::TrackRestart),
+	("n", Signal::QueueNext),
+	("a", Signal::QueueEnd),
+	("right", Signal::TrackNext),
+	("left", Signal::TrackBack),
+	("up", Signal::VolumeIncrease),
+	("down", Signal::VolumeDecrease),
+	("space", Signal::Play),
+	("enter", Signal::Play),
+	("pagedown", Signal::PlaylistNext),
+	("pageup", Signal::PlaylistBack),
+	("scrollup", Signal::VolumeIncrease),
+	("scrolldown", Signal::VolumeDecrease),
+	("click", Signal::Play),
+];
+
+#[cfg(feature = "tui")]
+/// The bindings that replace some of the default ones, when the full-screen interface is built in.
+const TUI_BINDINGS: [(&str, Signal); 3] = [
+	("down", Signal::SelectionNext),
+	("up", Signal::SelectionBack),
+	("enter", Signal::SelectionPlay),
+];
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[derive(Default)]
+/// The validated configuration.
+///
+/// Unset values are [`None`], so that the caller can tell them apart from explicit ones.
+pub struct Config {
+	flags: Option<String>,
+	volume: Option<f32>,
+	step: Option<f32>,
+	shuffle: Option<SerDeVary>,
+	library: Vec<PathBuf>,
+	status: Option<String>,
+	mouse: Option<bool>,
+	on_error: Option<ErrorPolicy>,
+	keys: KeyMap,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+/// The configuration, as it is written in the file.
+struct SerDeConfig {
+	/// Flags that are always passed (e.g.: `fn`), merged with the ones on the command line.
+	flags: Option<String>,
+	/// The initial volume, also the one that the volume is reset to.
+	volume: Option<f32>,
+	/// The amount by which the volume changes per volume [`Signal`].
+	step: Option<f32>,
+	/// The shuffling of playlists that do not set it themselves, like a playlist's `vary` field.
+	shuffle: Option<SerDeVary>,
+	/// Directories in which arguments are looked up, if they are not found in the working directory.
+	library: Vec<String>,
+	/// The format of the status line (e.g.: `{track} {position}`), no status line is printed if unset.
+	status: Option<String>,
+	/// Whether mouse inputs are read, which keeps the terminal from selecting text.
+	mouse: Option<bool>,
+	/// What is done with tracks that cannot be played.
+	on_error: Option<ErrorPolicy>,
+	/// Key chords (e.g.: `ctrl+l`), mapped to [`Signal`] names (e.g.: `playlist-next`).
+	keys: BTreeMap<String, String>,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[derive(Clone)]
+/// A map from key chords to [`Signals`].
+///
+/// [`Signals`]: Signal
+pub struct KeyMap {
+	bindings: HashMap<Chord, Signal>,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+/// A key or mouse input, together with the modifiers that are held down.
+///
+/// The shift modifier is folded into the case of characters, and characters that are held with control or alt are lower-case.\
+/// This way, `ctrl+l` and `ctrl+L` are the same chord, and `L` is the same as `shift+l`.
+struct Chord {
+	trigger: Trigger,
+	modifiers: KeyModifiers,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+/// The non-modifier part of a [`Chord`].
+enum Trigger {
+	Key(KeyCode),
+	ScrollUp,
+	ScrollDown,
+	/// Pressing a mouse button.
+	Click(MouseButton),
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+impl Config {
+	/// Load the configuration from the configuration directory.
+	///
+	/// A missing file results in the default configuration.
+	pub fn try_new() -> Result<Self, Error> {
+		match read_to_string(config_path(FILE_NAME)?) {
+			Ok(contents) => Self::try_from_contents(&contents),
+			Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+			Err(error) => Err(error)?,
+		}
+	}
+
+	#[inline(always)]
+	/// Load the configuration from an arbitrary file (e.g.: one passed with `--config`).
+	///
+	/// Unlike with [`try_new`], the file must exist.
+	///
+	/// [`try_new`]: Self::try_new
+	pub fn try_from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+		Self::try_from_contents(&read_to_string(path)?)
+	}
+
+	/// Parse and validate the configuration.
+	///
+	/// # Errors:
+	///
+	/// - The volume is not between 0 and 2, or the step is not between 0 (exclusive) and 2 ([`ConfigError::Range`]).
+	/// - A library directory does not exist.
+	/// - See [`KeyMap`]'s [`TryFrom`] implementation.
+	fn try_from_contents(contents: &str) -> Result<Self, Error> {
+		let SerDeConfig {
+			flags,
+			volume,
+			step,
+			shuffle,
+			library,
+			status,
+			mouse,
+			on_error,
+			keys,
+		} = from_str(contents)?;
+		if volume.is_some_and(|volume| !(0.0..=2.0).contains(&volume)) {
+			Err(ConfigError::Range("volume", "between 0 and 2"))?
+		}
+		if step.is_some_and(|step| step <= 0.0 || step > 2.0) {
+			Err(ConfigError::Range("step", "above 0, and at most 2"))?
+		}
+		Ok(Self {
+			flags,
+			volume,
+			step,
+			shuffle,
+			library: library
+				.iter()
+				.map(fmt_path)
+				.collect::<Result<Vec<PathBuf>, Error>>()?,
+			status,
+			mouse,
+			on_error,
+			keys: KeyMap::try_from(keys)?,
+		})
+	}
+
+	#[inline(always)]
+	pub fn flags_get(&self) -> Option<&str> { self.flags.as_deref() }
+
+	#[inline(always)]
+	pub fn volume_get(&self) -> Option<f32> { self.volume }
+
+	#[inline(always)]
+	pub fn step_get(&self) -> Option<f32> { self.step }
+
+	#[inline(always)]
+	pub fn shuffle_get(&self) -> Option<SerDeVary> { self.shuffle }
+
+	#[inline(always)]
+	pub fn library_get(&self) -> &[PathBuf] { &self.library }
+
+	#[inline(always)]
+	pub fn status_get(&self) -> Option<&str> { self.status.as_deref() }
+
+	#[inline(always)]
+	/// Whether mouse inputs should be read, which is the default.
+	pub fn mouse_is(&self) -> bool { self.mouse.unwrap_or(true) }
+
+	#[inline(always)]
+	pub fn error_policy_get(&self) -> Option<ErrorPolicy> { self.on_error }
+
+	#[inline(always)]
+	pub fn keys_get(&self) -> &KeyMap { &self.keys }
+
+	#[inline(always)]
+	pub fn keys_take(self) -> KeyMap { self.keys }
+}
+
+impl KeyMap {
+	#[inline]
+	/// Get the [`Signal`] that a key press is bound to.
+	pub fn signal_get(&self, event: KeyEvent) -> Option<Signal> {
+		self.bindings
+			.get(&Chord::from(event))
+			.copied()
+	}
+
+	#[inline]
+	/// Get the [`Signal`] that a mouse input is bound to.
+	///
+	/// Only scrolling and pressing buttons can be bound, other mouse inputs (e.g.: movement) are never.
+	pub fn mouse_signal_get(&self, event: MouseEvent) -> Option<Signal> {
+		let trigger = match event.kind {
+			MouseEventKind::ScrollUp => Trigger::ScrollUp,
+			MouseEventKind::ScrollDown => Trigger::ScrollDown,
+			MouseEventKind::Down(button) => Trigger::Click(button),
+			_ => return None,
+		};
+		self.bindings
+			.get(&Chord::new(trigger, event.modifiers))
+			.copied()
+	}
+}
+
+impl Default for KeyMap {
+	fn default() -> Self {
+		#[cfg(feature = "tui")]
+		let defaults = DEFAULT_BINDINGS
+			.iter()
+			.chain(TUI_BINDINGS.iter());
+		#[cfg(not(feature = "tui"))]
+		let defaults = DEFAULT_BINDINGS.iter();
+		let bindings = defaults
+			.map(|(chord, signal)| (Chord::try_from(*chord).unwrap_or_else(|_| panic!("parse a default key binding  {chord}")), *signal))
+			.collect();
+		Self { bindings }
+	}
+}
+
+impl TryFrom<BTreeMap<String, String>> for KeyMap {
+	type Error = ConfigError;
+
+	/// Lay the given bindings over the default ones.
+	///
+	/// A binding of a chord that is bound by default replaces the default one (which is logged, if it was bound to another [`Signal`]).
+	///
+	/// # Errors:
+	///
+	/// - A chord cannot be parsed ([`ConfigError::Key`]).
+	/// - A [`Signal`] name is unknown ([`ConfigError::Signal`]).
+	/// - Two chords are spelled differently, but mean the same ([`ConfigError::Duplicate`]).
+	fn try_from(bindings: BTreeMap<String, String>) -> Result<Self, Self::Error> {
+		let mut map = Self::default();
+		let mut spellings: HashMap<Chord, String> = HashMap::new();
+		for (text, name) in bindings {
+			let chord = Chord::try_from(text.as_str())?;
+			let signal = Signal::from_name(&name).ok_or_else(|| ConfigError::Signal(text.clone(), name))?;
+			if let Some(replaced) = map
+				.bindings
+				.insert(chord, signal)
+				.filter(|replaced| replaced.name() != signal.name())
+			{
+				log!(Info; "the key '{text}' is bound to '{}', instead of '{}'", signal.name(), replaced.name())
+			}
+			if let Some(first) = spellings.insert(chord, text.clone()) {
+				Err(ConfigError::Duplicate(first, text))?
+			}
+		}
+		Ok(map)
+	}
+}
+
+impl Chord {
+	/// Bring the input and its modifiers into their canonical form.
+	fn new(trigger: Trigger, modifiers: KeyModifiers) -> Self {
+		let mut modifiers = modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+		let trigger = match trigger {
+			Trigger::Key(KeyCode::Char(symbol)) => {
+				modifiers.remove(KeyModifiers::SHIFT);
+				if modifiers.is_empty() {
+					Trigger::Key(KeyCode::Char(symbol))
+				} else {
+					Trigger::Key(KeyCode::Char(symbol.to_ascii_lowercase()))
+				}
+			},
+			other => other,
+		};
+		Self { trigger, modifiers }
+	}
+}
+
+impl From<KeyEvent> for Chord {
+	#[inline(always)]
+	fn from(event: KeyEvent) -> Self {
+		Self::new(Trigger::Key(event.code), event.modifiers)
+	}
+}
+
+impl TryFrom<&str> for Chord {
+	type Error = ConfigError;
+
+	/// Parse a chord, like `ctrl+l`, `alt+space`, `L`, `f5`, `alt++` or `ctrl+scrollup`.
+	///
+	/// Modifiers (`ctrl`, `alt`, `shift`) and named keys are case-insensitive, single characters are not.
+	fn try_from(text: &str) -> Result<Self, Self::Error> {
+		let invalid = || ConfigError::Key(String::from(text));
+		let (prefix, key) = match text.strip_suffix("++") {
+			Some(prefix) => (prefix, "+"),
+			None if text == "+" => ("", "+"),
+			None => text
+				.rsplit_once('+')
+				.unwrap_or(("", text)),
+		};
+
+		let mut modifiers = KeyModifiers::NONE;
+		for modifier in prefix
+			.split('+')
+			.filter(|modifier| !modifier.is_empty())
+		{
+			modifiers |= match modifier
+				.to_ascii_lowercase()
+				.as_str()
+			{
+				"ctrl" | "control" => KeyModifiers::CONTROL,
+				"alt" => KeyModifiers::ALT,
+				"shift" => KeyModifiers::SHIFT,
+				_ => Err(invalid())?,
+			}
+		}
+
+		let mut characters = key.chars();
+		let trigger = match (characters.next(), characters.next()) {
+			(Some(symbol), None) if modifiers.contains(KeyModifiers::SHIFT) => Trigger::Key(KeyCode::Char(symbol.to_ascii_uppercase())),
+			(Some(symbol), None) => Trigger::Key(KeyCode::Char(symbol)),
+			_ => match key
+				.to_ascii_lowercase()
+				.as_str()
+			{
+				"space" => Trigger::Key(KeyCode::Char(' ')),
+				"enter" | "return" => Trigger::Key(KeyCode::Enter),
+				"tab" => Trigger::Key(KeyCode::Tab),
+				"backtab" => Trigger::Key(KeyCode::BackTab),
+				"backspace" => Trigger::Key(KeyCode::Backspace),
+				"esc" | "escape" => Trigger::Key(KeyCode::Esc),
+				"up" => Trigger::Key(KeyCode::Up),
+				"down" => Trigger::Key(KeyCode::Down),
+				"left" => Trigger::Key(KeyCode::Left),
+				"right" => Trigger::Key(KeyCode::Right),
+				"home" => Trigger::Key(KeyCode::Home),
+				"end" => Trigger::Key(KeyCode::End),
+				"pageup" => Trigger::Key(KeyCode::PageUp),
+				"pagedown" => Trigger::Key(KeyCode::PageDown),
+				"insert" => Trigger::Key(KeyCode::Insert),
+				"delete" => Trigger::Key(KeyCode::Delete),
+				"scrollup" => Trigger::ScrollUp,
+				"scrolldown" => Trigger::ScrollDown,
+				"click" => Trigger::Click(MouseButton::Left),
+				"rightclick" => Trigger::Click(MouseButton::Right),
+				"middleclick" => Trigger::Click(MouseButton::Middle),
+				name => match name
+					.strip_prefix('f')
+					.and_then(|number| number.parse().ok())
+				{
+					Some(number @ 1..=24) => Trigger::Key(KeyCode::F(number)),
+					_ => Err(invalid())?,
+				},
+			},
+		};
+		Ok(Self::new(trigger, modifiers))
+	}
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Parse a chord, which is expected to be valid.
+	fn chord(text: &str) -> Chord {
+		Chord::try_from(text).unwrap()
+	}
+
+	/// Lay the given bindings over the default ones.
+	fn keys_parse(bindings: &[(&str, &str)]) -> Result<KeyMap, ConfigError> {
+		KeyMap::try_from(
+			bindings
+				.iter()
+				.map(|(chord, name)| (String::from(*chord), String::from(*name)))
+				.collect::<BTreeMap<String, String>>(),
+		)
+	}
+
+	#[test]
+	fn chord_modifiers() {
+		assert_eq!(chord("ctrl+l"), Chord::new(Trigger::Key(KeyCode::Char('l')), KeyModifiers::CONTROL));
+		assert_eq!(chord("Control+Alt+space"), Chord::new(Trigger::Key(KeyCode::Char(' ')), KeyModifiers::CONTROL | KeyModifiers::ALT));
+		assert_eq!(chord("alt++"), Chord::new(Trigger::Key(KeyCode::Char('+')), KeyModifiers::ALT));
+		assert_eq!(chord("ctrl+scrollup"), Chord::new(Trigger::ScrollUp, KeyModifiers::CONTROL));
+	}
+
+	#[test]
+	fn chord_shift_folding() {
+		assert_eq!(chord("shift+l"), chord("L"));
+		assert_eq!(chord("ctrl+L"), chord("ctrl+l"));
+		assert_ne!(chord("l"), chord("L"));
+		assert_eq!(chord("shift+right"), Chord::new(Trigger::Key(KeyCode::Right), KeyModifiers::SHIFT));
+	}
+
+	#[test]
+	fn chord_named_keys() {
+		assert_eq!(chord("F5"), Chord::new(Trigger::Key(KeyCode::F(5)), KeyModifiers::NONE));
+		assert_eq!(chord("return"), chord("enter"));
+		assert_eq!(chord("rightclick"), Chord::new(Trigger::Click(MouseButton::Right), KeyModifiers::NONE));
+	}
+
+	#[test]
+	fn chord_invalid() {
+		for text in ["", "hyper+l", "ctrl+", "f25", "f0", "nothing", "ctrl+nothing"] {
+			assert_eq!(Chord::try_from(text), Err(ConfigError::Key(String::from(text))), "{text}")
+		}
+	}
+
+	#[test]
+	fn keys_over_defaults() {
+		let keys = keys_parse(&[("ctrl+q", "exit"), ("l", "mute")]).unwrap();
+		let signal = |code, modifiers| {
+			keys.signal_get(KeyEvent::new(code, modifiers))
+				.map(|signal| signal.name())
+		};
+		assert_eq!(signal(KeyCode::Char('q'), KeyModifiers::CONTROL), Some("exit"));
+		// NOTE: a user binding replaces the default one of the same chord.
+		assert_eq!(signal(KeyCode::Char('l'), KeyModifiers::NONE), Some("mute"));
+		assert_eq!(signal(KeyCode::Char('k'), KeyModifiers::NONE), Some("play"));
+		assert_eq!(signal(KeyCode::Char('q'), KeyModifiers::NONE), None);
+	}
+
+	#[test]
+	fn keys_unknown_signal() {
+		assert_eq!(
+			keys_parse(&[("q", "explode")]).err(),
+			Some(ConfigError::Signal(String::from("q"), String::from("explode")))
+		);
+	}
+
+	#[test]
+	fn keys_unknown_key() {
+		assert_eq!(keys_parse(&[("meta+q", "exit")]).err(), Some(ConfigError::Key(String::from("meta+q"))));
+	}
+
+	#[test]
+	fn keys_duplicate() {
+		assert_eq!(
+			keys_parse(&[("L", "exit"), ("shift+l", "mute")]).err(),
+			Some(ConfigError::Duplicate(String::from("L"), String::from("shift+l")))
+		);
+	}
+}
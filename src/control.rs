@@ -0,0 +1,315 @@
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+//! A local control socket, for controlling a running instance from the outside.
+//!
+//! The socket lies in the runtime directory, and speaks a line based protocol.\
+//! Every line is a command, either as plain text (e.g.: `track-next`) or as a JSON object (e.g.: `{"command": "track-next"}`).\
+//! Every command is answered with a single line JSON object, that at least contains the `ok` field.
+//!
+//! Commands are either [`Signal`] names, or one of the following queries:
+//! - `track`: the path of the playing file.
+//! - `position`: the position inside of the playing file, in seconds.
+//! - `volume`: the current volume.
+//! - `status`: all of the above, and more.
+//!
+//! The jump [`Signals`] take the number to jump to as their only argument (e.g.: `track-jump 5`); without it, they jump to the number that was last entered.
+//!
+//! The `subscribe` command turns the connection into a stream of [`Events`], one JSON object per line, until it is closed.
+//!
+//! [`Events`]: Event
+//! [`Signals`]: Signal
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+use super::{
+	events::Event,
+	in_out::Signal,
+	utilities::runtime_path,
+	Error,
+};
+use crossbeam_channel::{self as channel, Sender};
+use serde::{Deserialize, Serialize};
+use serde_json::{from_str, json, to_string, to_value, Value};
+use std::{
+	fs::remove_file,
+	io::{BufRead, BufReader, ErrorKind, Write},
+	os::unix::net::{UnixListener, UnixStream},
+	path::PathBuf,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc, Mutex,
+	},
+	thread::Builder,
+};
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// The name of the socket, in the runtime directory.
+const SOCKET_NAME: &str = concat!(env!("CARGO_PKG_NAME"), ".sock");
+
+/// The name of the file, in the runtime directory, that holds the process identifier of a background instance.
+const PID_NAME: &str = concat!(env!("CARGO_PKG_NAME"), ".pid");
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// The server side of the control socket.
+///
+/// The socket is accepted on in a separate thread, and every connection is handled in its own thread.\
+/// The socket file is removed once the server is dropped.
+pub struct Server {
+	socket_path: PathBuf,
+	status: Arc<Mutex<Status>>,
+	/// One [`Sender`] per subscribed connection.
+	subscribers: Arc<Mutex<Vec<Sender<Event>>>>,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[derive(Serialize)]
+#[derive(Clone, Default)]
+/// A snapshot of the player's state, as answered to queries.
+pub struct Status {
+	/// The path of the playing file.
+	pub track: Option<PathBuf>,
+	/// The names of the playing file, as read from its tags, or overridden in its playlist file.
+	pub title: Option<String>,
+	pub artist: Option<String>,
+	pub album: Option<String>,
+	/// The position inside of the playing file, in seconds.
+	pub position: f64,
+	pub volume: f32,
+	pub paused: bool,
+	/// The playlist-pointer.
+	pub playlist: usize,
+	/// The track-pointer.
+	pub index: usize,
+}
+
+#[derive(Deserialize)]
+/// A command in its JSON form.
+struct Request {
+	command: String,
+	#[serde(default)]
+	arguments: Vec<String>,
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+impl Server {
+	/// Bind the control socket, and start accepting connections.
+	///
+	/// Accepted [`Signals`] are sent through the given [`Sender`].\
+	/// The number that a jump [`Signal`] is given is stored into the `target`, before the [`Signal`] is sent.\
+	/// A left-over socket of an instance that is not running anymore is replaced.
+	///
+	/// # Errors:
+	///
+	/// - The socket is already in use by a running instance ([`ErrorKind::AddrInUse`]).
+	///
+	/// [`Signals`]: Signal
+	pub fn try_new(signal_sender: Sender<Signal>, target: Arc<AtomicUsize>) -> Result<Self, Error> {
+		let socket_path = socket_path()?;
+		let listener = match UnixListener::bind(&socket_path) {
+			Ok(listener) => listener,
+			Err(error) if error.kind() == ErrorKind::AddrInUse => {
+				if UnixStream::connect(&socket_path).is_ok() {
+					Err(error)?
+				}
+				remove_file(&socket_path)?;
+				UnixListener::bind(&socket_path)?
+			},
+			Err(error) => Err(error)?,
+		};
+		let status = Arc::new(Mutex::new(Status::default()));
+		let subscribers = Arc::new(Mutex::new(Vec::new()));
+
+		let shared = (Arc::clone(&status), Arc::clone(&subscribers));
+		Builder::new()
+			.name(String::from("Server"))
+			.spawn(move || {
+				for stream in listener
+					.incoming()
+					.flatten()
+				{
+					let signal_sender = signal_sender.clone();
+					let (status, subscribers, target) = (Arc::clone(&shared.0), Arc::clone(&shared.1), Arc::clone(&target));
+					let _ = Builder::new()
+						.name(String::from("Connection"))
+						.spawn(move || connection_handle(stream, signal_sender, target, status, subscribers));
+				}
+			})?;
+		Ok(Self {
+			socket_path,
+			status,
+			subscribers,
+		})
+	}
+
+	/// Stream an [`Event`] to every subscribed connection.
+	///
+	/// Connections that have been closed are forgotten.
+	pub fn event_emit(&self, event: Event) {
+		if let Ok(mut subscribers) = self
+			.subscribers
+			.lock()
+		{
+			subscribers.retain(|subscriber| {
+				subscriber
+					.send(event.clone())
+					.is_ok()
+			})
+		}
+	}
+
+	#[inline]
+	/// Replace the snapshot that queries are answered with.
+	pub fn status_set(&self, status: Status) {
+		if let Ok(mut old) = self
+			.status
+			.lock()
+		{
+			*old = status
+		}
+	}
+}
+
+impl Drop for Server {
+	fn drop(&mut self) {
+		let _ = remove_file(&self.socket_path);
+	}
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[inline(always)]
+/// Get the path of the control socket.
+pub fn socket_path() -> Result<PathBuf, Error> {
+	runtime_path(SOCKET_NAME)
+}
+
+#[inline(always)]
+/// Get the path of the file that holds the process identifier of a background instance.
+pub fn pid_path() -> Result<PathBuf, Error> {
+	runtime_path(PID_NAME)
+}
+
+/// Send a single command to a running instance, and return its answer.
+///
+/// This is the client side of the control socket.
+pub fn request(command: &str) -> Result<String, Error> {
+	let mut stream = UnixStream::connect(socket_path()?)?;
+	writeln!(stream, "{command}")?;
+	let mut answer = String::new();
+	BufReader::new(stream).read_line(&mut answer)?;
+	Ok(answer)
+}
+
+/// Subscribe to the [`Events`] of a running instance.
+///
+/// Returns the raw JSON lines, as they arrive.
+///
+/// [`Events`]: Event
+pub fn subscribe() -> Result<impl Iterator<Item = String>, Error> {
+	let mut stream = UnixStream::connect(socket_path()?)?;
+	writeln!(stream, "subscribe")?;
+	let mut lines = BufReader::new(stream).lines();
+	lines
+		.next()
+		.transpose()?;
+	Ok(lines.map_while(Result::ok))
+}
+
+/// Answer the commands of a single connection, until it is closed or subscribes.
+fn connection_handle(
+	stream: UnixStream,
+	signal_sender: Sender<Signal>,
+	target: Arc<AtomicUsize>,
+	status: Arc<Mutex<Status>>,
+	subscribers: Arc<Mutex<Vec<Sender<Event>>>>,
+) {
+	let Ok(mut writer) = stream.try_clone() else { return };
+	for line in BufReader::new(stream)
+		.lines()
+		.map_while(Result::ok)
+	{
+		let line = line.trim();
+		if line == "subscribe" || from_str::<Request>(line).is_ok_and(|request| request.command == "subscribe") {
+			return subscription_handle(writer, &subscribers);
+		}
+		let answer = command_answer(line, &signal_sender, &target, &status);
+		if writeln!(writer, "{answer}").is_err() {
+			return;
+		}
+	}
+}
+
+/// Stream [`Events`] into a connection, until it is closed.
+///
+/// [`Events`]: Event
+fn subscription_handle(mut writer: UnixStream, subscribers: &Mutex<Vec<Sender<Event>>>) {
+	let (event_sender, event_receiver) = channel::unbounded();
+	match subscribers.lock() {
+		Ok(mut subscribers) => subscribers.push(event_sender),
+		Err(_) => return,
+	}
+	if writeln!(writer, "{}", json!({ "ok": true })).is_err() {
+		return;
+	}
+	for event in event_receiver {
+		let Ok(line) = to_string(&event) else { continue };
+		if writeln!(writer, "{line}").is_err() {
+			return;
+		}
+	}
+}
+
+/// Parse and execute a single command.
+fn command_answer(line: &str, signal_sender: &Sender<Signal>, target: &AtomicUsize, status: &Mutex<Status>) -> Value {
+	let Request { command, arguments } = if line.starts_with('{') {
+		match from_str(line) {
+			Ok(request) => request,
+			Err(why) => return json!({ "ok": false, "error": why.to_string() }),
+		}
+	} else {
+		let mut parts = line.split_whitespace();
+		Request {
+			command: parts
+				.next()
+				.map(String::from)
+				.unwrap_or_default(),
+			arguments: parts
+				.map(String::from)
+				.collect(),
+		}
+	};
+
+	if let Some(signal) = Signal::from_name(&command) {
+		match (signal.is_jump(), arguments.as_slice()) {
+			(_, []) => {},
+			(true, [number]) => match number.parse() {
+				Ok(number) => target.store(number, Ordering::Relaxed),
+				Err(_) => return json!({ "ok": false, "error": format!("'{number}' is not a number") }),
+			},
+			(true, _) => return json!({ "ok": false, "error": format!("'{command}' takes at most one number") }),
+			(false, _) => return json!({ "ok": false, "error": format!("'{command}' takes no arguments") }),
+		}
+		return match signal_sender.send(signal) {
+			Ok(()) => json!({ "ok": true }),
+			Err(_) => json!({ "ok": false, "error": "the player has stopped" }),
+		};
+	}
+	if !arguments.is_empty() {
+		return json!({ "ok": false, "error": format!("'{command}' takes no arguments") });
+	}
+	let Ok(snapshot) = status
+		.lock()
+		.map(|status| status.clone())
+	else {
+		return json!({ "ok": false, "error": "the status is unavailable" });
+	};
+	let Ok(Value::Object(mut fields)) = to_value(snapshot) else {
+		return json!({ "ok": false, "error": "the status is unavailable" });
+	};
+	match command.as_str() {
+		"status" => {
+			fields.insert(String::from("ok"), Value::Bool(true));
+			Value::Object(fields)
+		},
+		"track" | "position" | "volume" => {
+			let mut answer = json!({ "ok": true });
+			answer[&command] = fields
+				.remove(&command)
+				.unwrap_or_default();
+			answer
+		},
+		_ => json!({ "ok": false, "error": format!("unknown command '{command}'") }),
+	}
+}
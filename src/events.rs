@@ -0,0 +1,38 @@
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+//! Changes of the player's state.
+//!
+//! Events are streamed to the subscribers of the control socket, as one JSON object per line (e.g.: `{"event":"paused","paused":true}`).
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+use serde::Serialize;
+use std::path::PathBuf;
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[derive(Serialize)]
+#[derive(Clone)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+/// Changes of the player's state, as streamed to subscribers of the control socket.
+pub enum Event {
+	TrackStarted {
+		track: PathBuf,
+	},
+	TrackFinished {
+		track: PathBuf,
+		/// The time that was actually listened to, in seconds.
+		listened: f64,
+		/// Whether the [`Track`] was interrupted before its end.
+		///
+		/// [`Track`]: crate::playback::Track
+		skipped: bool,
+	},
+	Paused {
+		paused: bool,
+	},
+	VolumeChanged {
+		volume: f32,
+	},
+	PlaylistChanged {
+		/// The new playlist-pointer.
+		playlist: usize,
+	},
+	Exit,
+}
@@ -0,0 +1,192 @@
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+//! A named pipe (FIFO), for controlling a running instance with nothing but `echo`.
+//!
+//! The pipe lies in the runtime directory, and every line written into it is a command.\
+//! Unlike the [control socket], nothing is ever answered; unknown commands are ignored.
+//!
+//! Commands are either [`Signal`] names, or one of the following short-hands:
+//! - `next`, `back` (or `previous`): skip a track.
+//! - `pause` (or `toggle`): play or pause.
+//! - `restart`, `reset`, `quit` (or `stop`), `mute`.
+//! - `vol +<n>`, `vol -<n>`: change the volume by `n` percent.
+//!
+//! [control socket]: crate::control
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+use super::{in_out::Signal, utilities::runtime_path, Error};
+use crossbeam_channel::Sender;
+use std::{
+	ffi::CString,
+	fs::{remove_file, File, OpenOptions},
+	io::{self, BufRead, BufReader, ErrorKind},
+	os::unix::{ffi::OsStrExt, fs::OpenOptionsExt},
+	path::PathBuf,
+	thread::Builder,
+};
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// The name of the pipe, in the runtime directory.
+const FIFO_NAME: &str = concat!(env!("CARGO_PKG_NAME"), ".fifo");
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// The reading side of the control pipe.
+///
+/// The pipe is read in a separate thread, which re-opens it every time the last writer closes it.\
+/// The pipe file is removed once the structure is dropped.
+pub struct Fifo {
+	fifo_path: PathBuf,
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+impl Fifo {
+	/// Create the control pipe, and start reading commands from it.
+	///
+	/// Read commands are translated into [`Signals`], which are sent through the given [`Sender`].\
+	/// The `step` is the amount by which the volume changes per volume [`Signal`], so that `vol` commands can be translated.\
+	/// A left-over pipe of an instance that is not running anymore is replaced.
+	///
+	/// # Errors:
+	///
+	/// - The pipe is already read by a running instance ([`ErrorKind::AddrInUse`]).
+	///
+	/// [`Signals`]: Signal
+	pub fn try_new(signal_sender: Sender<Signal>, step: f32) -> Result<Self, Error> {
+		let fifo_path = fifo_path()?;
+		// NOTE: opening a pipe for writing, without blocking, only succeeds while something reads from it.
+		if OpenOptions::new()
+			.write(true)
+			.custom_flags(libc::O_NONBLOCK)
+			.open(&fifo_path)
+			.is_ok()
+		{
+			Err(io::Error::from(ErrorKind::AddrInUse))?
+		}
+		match remove_file(&fifo_path) {
+			Err(error) if error.kind() != ErrorKind::NotFound => Err(error)?,
+			_ => {},
+		}
+		let raw_path = CString::new(
+			fifo_path
+				.as_os_str()
+				.as_bytes(),
+		)
+		.map_err(|_| io::Error::from(ErrorKind::InvalidInput))?;
+		// SAFETY: `raw_path` is a valid, nul-terminated string that outlives the call.
+		if unsafe { libc::mkfifo(raw_path.as_ptr(), 0o600) } != 0 {
+			Err(io::Error::last_os_error())?
+		}
+
+		let reader_path = fifo_path.clone();
+		Builder::new()
+			.name(String::from("Fifo"))
+			.spawn(move || {
+				// NOTE: opening blocks until a writer shows up, and reading ends once the last writer is gone.
+				while let Ok(file) = File::open(&reader_path) {
+					for line in BufReader::new(file)
+						.lines()
+						.map_while(Result::ok)
+					{
+						for signal in signals_parse(&line, step)
+							.into_iter()
+							.flatten()
+						{
+							if signal_sender
+								.send(signal)
+								.is_err()
+							{
+								return;
+							}
+						}
+					}
+				}
+			})?;
+		Ok(Self { fifo_path })
+	}
+}
+
+impl Drop for Fifo {
+	fn drop(&mut self) {
+		let _ = remove_file(&self.fifo_path);
+	}
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[inline(always)]
+/// Get the path of the control pipe.
+pub fn fifo_path() -> Result<PathBuf, Error> {
+	runtime_path(FIFO_NAME)
+}
+
+/// Translate a single command into the [`Signals`] that it stands for.
+///
+/// The `step` is the amount by which the volume changes per volume [`Signal`].\
+/// Returns [`None`] for unknown or malformed commands.
+///
+/// [`Signals`]: Signal
+pub fn signals_parse(line: &str, step: f32) -> Option<Vec<Signal>> {
+	let mut parts = line.split_whitespace();
+	let command = parts.next()?;
+	let argument = parts.next();
+	if parts
+		.next()
+		.is_some()
+	{
+		return None;
+	}
+
+	let signal = match (command, argument) {
+		("vol" | "volume", Some(change)) => {
+			let percent: f32 = change
+				.parse()
+				.ok()?;
+			let signal = if percent < 0.0 { Signal::VolumeDecrease } else { Signal::VolumeIncrease };
+			// NOTE: any change at all is at least one step.
+			let count = ((percent.abs() / (step * 100.0)).round() as usize).max(usize::from(percent != 0.0));
+			return Some(vec![signal; count]);
+		},
+		(_, Some(_)) => return None,
+		("next", None) => Signal::TrackNext,
+		("back" | "previous", None) => Signal::TrackBack,
+		("pause" | "toggle", None) => Signal::Play,
+		("restart", None) => Signal::TrackRestart,
+		("reset", None) => Signal::TrackReset,
+		("quit" | "stop", None) => Signal::Exit,
+		(name, None) => Signal::from_name(name)?,
+	};
+	Some(vec![signal])
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// The default volume step.
+	const STEP: f32 = 0.05;
+
+	#[test]
+	fn parse_short_hands() {
+		assert_eq!(signals_parse("next", STEP), Some(vec![Signal::TrackNext]));
+		assert_eq!(signals_parse("previous", STEP), Some(vec![Signal::TrackBack]));
+		assert_eq!(signals_parse("  toggle  ", STEP), Some(vec![Signal::Play]));
+		assert_eq!(signals_parse("stop", STEP), Some(vec![Signal::Exit]));
+	}
+
+	#[test]
+	fn parse_signal_names() {
+		assert_eq!(signals_parse("volume-increase", STEP), Some(vec![Signal::VolumeIncrease]));
+	}
+
+	#[test]
+	fn parse_volume() {
+		assert_eq!(signals_parse("vol +10", STEP), Some(vec![Signal::VolumeIncrease; 2]));
+		assert_eq!(signals_parse("volume -15", STEP), Some(vec![Signal::VolumeDecrease; 3]));
+		assert_eq!(signals_parse("vol +1", STEP), Some(vec![Signal::VolumeIncrease]));
+		assert_eq!(signals_parse("vol -1", STEP), Some(vec![Signal::VolumeDecrease]));
+		assert_eq!(signals_parse("vol 0", STEP), Some(Vec::new()));
+	}
+
+	#[test]
+	fn parse_malformed() {
+		assert_eq!(signals_parse("", STEP), None);
+		assert_eq!(signals_parse("unknown", STEP), None);
+		assert_eq!(signals_parse("vol", STEP), None);
+		assert_eq!(signals_parse("vol loud", STEP), None);
+		assert_eq!(signals_parse("next 2", STEP), None);
+		assert_eq!(signals_parse("vol +1 +2", STEP), None);
+	}
+}
@@ -0,0 +1,253 @@
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+//! A log of every played file, and statistics about it.
+//!
+//! The log is a plain text file inside of the state directory, which is only ever appended to.\
+//! Every line is one [`Record`], as `seconds<TAB>kind<TAB>milliseconds<TAB>path`: when it happened (since the unix epoch), what happened, how long was listened, and to what.
+//!
+//! A file counts as played if it was finished, or skipped (or quit) after at least [`PLAYED_THRESHOLD`].
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+use super::{utilities::state_path, Error};
+use std::{
+	collections::HashMap,
+	fs::{read_to_string, OpenOptions},
+	io::{ErrorKind, Write},
+	path::{Path, PathBuf},
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// The name of the log, in the state directory.
+const FILE_NAME: &str = "history";
+
+/// The time that a skipped file must have been listened to, to count as played.
+pub const PLAYED_THRESHOLD: Duration = Duration::from_secs(30);
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[derive(Clone, Copy)]
+/// What happened to a file.
+pub enum RecordKind {
+	/// Started playing, nothing was listened to yet.
+	Started,
+	/// Played to its end.
+	Finished,
+	/// Interrupted before its end.
+	Skipped,
+	/// Interrupted by quitting, which is not held against the file as a skip.
+	Exited,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[derive(Clone)]
+/// A single line of the log.
+pub struct Record {
+	/// When it happened, in seconds since the unix epoch.
+	pub time: u64,
+	pub kind: RecordKind,
+	/// The time that was actually listened to.
+	pub listened: Duration,
+	pub path: PathBuf,
+}
+
+/// The log of every played file.
+pub struct History {
+	/// Where the log is stored.
+	file_path: PathBuf,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[derive(Clone, Default)]
+/// The statistics of a single file.
+pub struct TrackStats {
+	pub path: PathBuf,
+	/// How often it was listened to, either finished, skipped or quit.
+	pub listens: usize,
+	/// How often it was played, see [`Record::is_play`].
+	pub plays: usize,
+	/// How often it was skipped (including skips that count as played).
+	pub skips: usize,
+	pub listened: Duration,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[derive(Clone, Default)]
+/// The statistics of the whole log.
+pub struct Stats {
+	/// The time that was listened to, in total.
+	pub listened: Duration,
+	pub finished: usize,
+	pub skipped: usize,
+	/// Every file that was listened to, the most often played first.
+	pub tracks: Vec<TrackStats>,
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+impl History {
+	#[inline(always)]
+	/// Use the log inside of the state directory.
+	pub fn try_new() -> Result<Self, Error> {
+		Ok(Self::from_path(state_path(FILE_NAME)?))
+	}
+
+	#[inline(always)]
+	/// Use an arbitrary file as the log.
+	pub fn from_path(file_path: PathBuf) -> Self {
+		Self { file_path }
+	}
+
+	/// Append a [`Record`] of a file, that happened now, to the log.
+	pub fn record(&self, kind: RecordKind, listened: Duration, path: &Path) -> Result<(), Error> {
+		let time = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map_or(0, |since| since.as_secs());
+		let mut file = OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(&self.file_path)?;
+		writeln!(file, "{time}\t{}\t{}\t{}", kind.name(), listened.as_millis(), path.display()).map_err(Error::from)
+	}
+
+	/// Read every [`Record`] of the log, oldest first.
+	///
+	/// A missing file is treated as an empty log, lines that cannot be parsed are ignored.
+	pub fn records_read(&self) -> Result<Vec<Record>, Error> {
+		let contents = match read_to_string(&self.file_path) {
+			Ok(contents) => contents,
+			Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+			Err(error) => Err(error)?,
+		};
+		Ok(contents
+			.lines()
+			.filter_map(Record::parse)
+			.collect())
+	}
+
+	/// Gather the [`Stats`] of the whole log.
+	pub fn stats_get(&self) -> Result<Stats, Error> {
+		let mut stats = Stats::default();
+		let mut tracks: HashMap<PathBuf, TrackStats> = HashMap::new();
+		for record in self.records_read()? {
+			let is_play = record.is_play();
+			let track = tracks
+				.entry(record.path)
+				.or_default();
+			match record.kind {
+				RecordKind::Started => continue,
+				RecordKind::Finished => stats.finished += 1,
+				RecordKind::Skipped => {
+					stats.skipped += 1;
+					track.skips += 1
+				},
+				RecordKind::Exited => {},
+			}
+			track.listens += 1;
+			track.plays += is_play as usize;
+			track.listened += record.listened;
+			stats.listened += record.listened;
+		}
+		stats.tracks = tracks
+			.into_iter()
+			.filter(|(_, track)| track.listens > 0)
+			.map(|(path, track)| TrackStats { path, ..track })
+			.collect();
+		stats.tracks
+			.sort_by(|one, other| {
+				other
+					.plays
+					.cmp(&one.plays)
+					.then_with(|| one.path.cmp(&other.path))
+			});
+		Ok(stats)
+	}
+
+	/// Count how often every file was played.
+	pub fn play_counts_get(&self) -> Result<HashMap<PathBuf, usize>, Error> {
+		let mut counts = HashMap::new();
+		for record in self
+			.records_read()?
+			.into_iter()
+			.filter(Record::is_play)
+		{
+			*counts
+				.entry(record.path)
+				.or_default() += 1
+		}
+		Ok(counts)
+	}
+}
+
+impl Stats {
+	#[inline]
+	/// Get the share of the listened files that were skipped, from 0 to 1.
+	pub fn skip_rate_get(&self) -> f64 {
+		match self.finished + self.skipped {
+			0 => 0.0,
+			total => self.skipped as f64 / total as f64,
+		}
+	}
+}
+
+impl TrackStats {
+	#[inline]
+	/// Get the share of the times that the file was listened to, that it was skipped, from 0 to 1.
+	pub fn skip_rate_get(&self) -> f64 {
+		match self.listens {
+			0 => 0.0,
+			listens => self.skips as f64 / listens as f64,
+		}
+	}
+}
+
+impl Record {
+	/// Parse a single line of the log.
+	fn parse(line: &str) -> Option<Self> {
+		let mut parts = line.splitn(4, '\t');
+		let time = parts
+			.next()?
+			.parse()
+			.ok()?;
+		let kind = RecordKind::from_name(parts.next()?)?;
+		let listened = Duration::from_millis(
+			parts
+				.next()?
+				.parse()
+				.ok()?,
+		);
+		let path = PathBuf::from(parts.next()?);
+		Some(Self { time, kind, listened, path })
+	}
+
+	#[inline]
+	/// Whether or not the [`Record`] counts as the file being played.
+	///
+	/// See [`PLAYED_THRESHOLD`].
+	pub fn is_play(&self) -> bool {
+		match self.kind {
+			RecordKind::Started => false,
+			RecordKind::Finished => true,
+			RecordKind::Skipped | RecordKind::Exited => self.listened >= PLAYED_THRESHOLD,
+		}
+	}
+}
+
+impl RecordKind {
+	#[inline]
+	/// Get the [`RecordKind`] with the given name.
+	pub fn from_name(name: &str) -> Option<Self> {
+		match name {
+			"started" => Some(Self::Started),
+			"finished" => Some(Self::Finished),
+			"skipped" => Some(Self::Skipped),
+			"exited" => Some(Self::Exited),
+			_ => None,
+		}
+	}
+
+	#[inline]
+	/// Get the name of `self` in the log.
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::Started => "started",
+			Self::Finished => "finished",
+			Self::Skipped => "skipped",
+			Self::Exited => "exited",
+		}
+	}
+}
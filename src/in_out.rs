@@ -3,14 +3,21 @@
 use std::fmt::{self, Debug, Formatter};
 use std::{
 	io::{Read, Seek},
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc, Mutex,
+	},
 	thread::{Builder, JoinHandle},
+	time::Duration,
 };
 
-use crossbeam_channel::{self as channel, Receiver, Sender, TryRecvError};
+use crossbeam_channel::{self as channel, Receiver, RecvTimeoutError, Sender, TryRecvError};
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
-use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink};
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
 
-use super::{ChannelError, Error};
+#[cfg(unix)]
+use super::control::{Server, Status};
+use super::{config::KeyMap, events, ChannelError, Error};
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 /// This is a default message that is used when a [`Sender`] or [`Receiver`] has
 /// hung up the connection.
@@ -18,6 +25,9 @@ use super::{ChannelError, Error};
 /// [`Sender`]: crossbeam_channel::Sender
 /// [`Receiver`]: crossbeam_channel::Receiver
 const DISCONNECTED: &str = "DISCONNECTED CHANNEL";
+
+/// How long the control thread waits for a terminal event, before checking whether it should exit.
+const POLL_TIMEOUT: Duration = Duration::from_millis(100);
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 /// Singleton bundled In- and Output constructs.
 ///
@@ -32,6 +42,9 @@ pub struct IOHandle {
 	sound_out: (OutputStream, OutputStreamHandle), // NOTE(by: @OST-Gh): Needs to be tuple, otherwise breaks
 	controls: Controls,
 	playback: Sink,
+	#[cfg(unix)]
+	/// The control socket, if no other instance already serves it.
+	server: Option<Server>,
 }
 
 #[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
@@ -46,9 +59,20 @@ pub struct IOHandle {
 ///
 /// [`signals`]: Signal
 pub struct Controls {
-	control_thread: JoinHandle<()>,
+	/// [`None`] when running without a terminal.
+	control_thread: Option<JoinHandle<()>>,
 	exit_notifier: Sender<()>,
+	/// Kept around, so that other sources of [`signals`] can be connected.
+	///
+	/// [`signals`]: Signal
+	signal_sender: Sender<Signal>,
 	signal_receiver: Receiver<Signal>,
+	/// The numeric entry that is being typed, shared with the control thread.
+	entry: Arc<Mutex<String>>,
+	/// The number of the last finished numeric entry, which the jump [`signals`] refer to.
+	///
+	/// [`signals`]: Signal
+	target: Arc<AtomicUsize>,
 }
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 #[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
@@ -61,6 +85,11 @@ pub struct Controls {
 #[derive(Clone, Copy)]
 /// High level control signal representation.
 pub enum Signal {
+	// 0 * 2^2 + 0 * 2^3
+	QueueNext      = 0b0001, // 1 * 2^0 + 0 * 2^1
+	QueueEnd       = 0b0010, // 0 * 2^0 + 1 * 2^1
+	TrackRestart   = 0b0011, // 1 * 2^0 + 1 * 2^1
+
 	// 1 * 2^2 + 0 * 2^3
 	PlaylistNext   = 0b0101, // 1 * 2^0 + 0 * 2^1
 	PlaylistBack   = 0b0110, // 0 * 2^0 + 1 * 2^1
@@ -78,6 +107,20 @@ pub enum Signal {
 	VolumeDecrease = 0b1110, // 0 * 2^0 + 1 * 2^1
 	Mute           = 0b1111, // 1 * 2^0 + 1 * 2^1
 	VolumeReset    = 0b1100, // 0 * 2^0 + 0 * 2^1
+
+	// 0 * 2^2 + 0 * 2^3 + 1 * 2^4
+	SelectionNext  = 0b10001, // 1 * 2^0 + 0 * 2^1
+	SelectionBack  = 0b10010, // 0 * 2^0 + 1 * 2^1
+	SelectionPlay  = 0b10011, // 1 * 2^0 + 1 * 2^1
+
+	// 1 * 2^2 + 0 * 2^3 + 1 * 2^4
+	TrackJump      = 0b10101, // 1 * 2^0 + 0 * 2^1
+	PlaylistJump   = 0b10110, // 0 * 2^0 + 1 * 2^1
+
+	// 0 * 2^2 + 1 * 2^3 + 1 * 2^4
+	SearchNext     = 0b11001, // 1 * 2^0 + 0 * 2^1
+	SearchBack     = 0b11010, // 0 * 2^0 + 1 * 2^1
+	SearchUpdate   = 0b11000, // 0 * 2^0 + 0 * 2^1
 }
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 impl IOHandle {
@@ -106,6 +149,28 @@ impl IOHandle {
 			.map_err(Error::Channel)
 	}
 
+	#[cfg(unix)]
+	#[inline]
+	/// Publish a snapshot of the player's state to the control socket.
+	pub fn status_set(&self, status: Status) {
+		if let Some(server) = &self.server {
+			server.status_set(status)
+		}
+	}
+
+	#[inline]
+	/// Stream an [`Event`] to the subscribers of the control socket.
+	///
+	/// [`Event`]: events::Event
+	pub fn event_emit(&self, event: events::Event) {
+		#[cfg(unix)]
+		if let Some(server) = &self.server {
+			server.event_emit(event)
+		}
+		#[cfg(not(unix))]
+		let _ = event;
+	}
+
 	#[inline(always)]
 	/// Get a reference to the underlying internal [`Sink`]
 	///
@@ -118,41 +183,82 @@ impl IOHandle {
 	/// A source is a read-, seek-able, synchronous source of bytes, that
 	/// can be interpreted as a common file encoding.\ See [`Decoder`]'s new
 	/// associated functions.
-	pub fn stream_play(&self, source: impl Read + Seek + Send + Sync + 'static) -> Result<(), Error> {
+	///
+	/// Returns the total duration of the source, if it is known.
+	pub fn stream_play(&self, source: impl Read + Seek + Send + Sync + 'static) -> Result<Option<Duration>, Error> {
 		let decoder = Decoder::new(source)?;
+		let length = decoder.total_duration();
 		self.playback
 			.append(decoder);
-		Ok(())
+		Ok(length)
 	}
 
-	/// Create a new [`IOHandle`] with an optional control-thread.
+	#[inline(always)]
+	/// Create a new [`IOHandle`] with a control-thread, that reads keyboard inputs from the terminal, using the default key bindings.
 	pub fn try_new() -> Result<Self, Error> {
+		Self::try_with_keys(KeyMap::default())
+	}
+
+	#[inline(always)]
+	/// Create a new [`IOHandle`] with a control-thread, that reads keyboard inputs from the terminal, using the given key bindings.
+	pub fn try_with_keys(keys: KeyMap) -> Result<Self, Error> {
+		Self::try_from_keys(Some(keys))
+	}
+
+	#[cfg(unix)]
+	#[inline(always)]
+	/// Create a new [`IOHandle`] without a control-thread, for running without a terminal.
+	///
+	/// [`Signals`] can then only be sent through the control socket, which is why failing to serve it is an error.
+	///
+	/// [`Signals`]: Signal
+	pub fn try_new_headless() -> Result<Self, Error> {
+		Self::try_from_keys(None)
+	}
+
+	/// Create a new [`IOHandle`] with an optional control-thread.
+	fn try_from_keys(keys: Option<KeyMap>) -> Result<Self, Error> {
+		let should_read_keys = keys.is_some();
 		let sound_out = rodio::OutputStream::try_default()?;
 
 		let (signal_sender, signal_receiver) = channel::unbounded();
 		let (exit_notifier, exit_receiver) = channel::unbounded();
+		let key_sender = signal_sender.clone();
+		let keys = keys.unwrap_or_default();
+		let entry = Arc::new(Mutex::new(String::new()));
+		let target = Arc::new(AtomicUsize::new(0));
+		let (key_entry, key_target) = (Arc::clone(&entry), Arc::clone(&target));
 		let key_handler = move || // NOTE(by: @OST-Gh): Pray to god that the caller actually joins the thread...
 		loop {
 			if !exit_receiver.is_empty() { return }
+			// NOTE: waiting for events with a timeout, so that an exit does not need another input to be noticed.
+			if !event::poll(POLL_TIMEOUT).unwrap_or_else(|why| panic!("poll an event from the current terminal  {why}")) { continue }
 			let signal = match event::read().unwrap_or_else(|why| panic!("read an event from the current terminal  {why}")) {
-				Event::Key(KeyEvent { code: KeyCode::Char('l' | 'L'), modifiers, .. }) if modifiers.contains(KeyModifiers::CONTROL) => Signal::PlaylistNext,
-				Event::Key(KeyEvent { code: KeyCode::Char('j' | 'J'), modifiers, .. }) if modifiers.contains(KeyModifiers::CONTROL) => Signal::PlaylistBack,
-				Event::Key(KeyEvent { code: KeyCode::Char('k' | 'K'), modifiers, .. }) if modifiers.contains(KeyModifiers::CONTROL) => Signal::Exit,
-				Event::Key(KeyEvent { code: KeyCode::Char('h' | 'H'), modifiers, .. }) if modifiers.contains(KeyModifiers::CONTROL) => Signal::PlaylistReset,
-
-				Event::Key(KeyEvent { code: KeyCode::Char('l'), ..}) => Signal::TrackNext,
-				Event::Key(KeyEvent { code: KeyCode::Char('j'), ..}) => Signal::TrackBack,
-				Event::Key(KeyEvent { code: KeyCode::Char('k'), ..}) => Signal::Play,
-				Event::Key(KeyEvent { code: KeyCode::Char('h'), ..}) => Signal::TrackReset,
-
-				Event::Key(KeyEvent { code: KeyCode::Char('L'), .. }) => Signal::VolumeIncrease,
-				Event::Key(KeyEvent { code: KeyCode::Char('J'), .. }) => Signal::VolumeDecrease,
-				Event::Key(KeyEvent { code: KeyCode::Char('K'), .. }) => Signal::Mute,
-				Event::Key(KeyEvent { code: KeyCode::Char('H'), .. }) => Signal::VolumeReset,
-
+				Event::Key(key) => {
+					let typed = match key_entry.lock() {
+						Ok(mut entry) => entry_type(&mut entry, key, keys.signal_get(key).is_some()),
+						Err(_) => Typed::Ignored,
+					};
+					match typed {
+						Typed::Finished(signal, number) => {
+							key_target.store(number, Ordering::Relaxed);
+							signal
+						},
+						Typed::Searched(signal) => signal,
+						Typed::Consumed => continue,
+						Typed::Ignored => match keys.signal_get(key) {
+							Some(signal) => signal,
+							None => continue,
+						},
+					}
+				},
+				Event::Mouse(mouse) => match keys.mouse_signal_get(mouse) {
+					Some(signal) => signal,
+					None => continue,
+				},
 				_ => continue,
 			};
-			if signal_sender
+			if key_sender
 				.send(signal)
 				.is_err()
 			{ panic!("send a signal to the playback {DISCONNECTED}") }
@@ -160,14 +266,32 @@ impl IOHandle {
 				return
 			}
 		};
-		let control_thread = Builder::new()
-			.name(String::from("Controls"))
-			.stack_size(8)
-			.spawn(key_handler)?;
+		let control_thread = if should_read_keys {
+			Some(Builder::new()
+				.name(String::from("Controls"))
+				.stack_size(8)
+				.spawn(key_handler)?)
+		} else {
+			None
+		};
 		let controls = Controls {
 			control_thread,
 			exit_notifier,
+			signal_sender,
 			signal_receiver,
+			entry,
+			target,
+		};
+
+		#[cfg(unix)]
+		let server = match Server::try_new(controls.signal_sender_get(), Arc::clone(&controls.target)) {
+			Ok(server) => Some(server),
+			// NOTE: the socket is optional while the keyboard controls the playback (e.g.: another instance is already being controlled through it).
+			Err(why) if should_read_keys => {
+				log!(Warn; "serving the control socket; {why}");
+				None
+			},
+			Err(why) => Err(why)?,
 		};
 
 		let playback = Sink::try_new(&sound_out.1)?;
@@ -177,6 +301,8 @@ impl IOHandle {
 			sound_out,
 			controls,
 			playback,
+			#[cfg(unix)]
+			server,
 		})
 	}
 }
@@ -224,9 +350,9 @@ impl Controls {
 	/// [`exit_notify`]: Self.exit_notify
 	/// [`controls_take`]: IOHandle.controls_take
 	pub fn clean_up(self) {
-		let _ = self
-			.control_thread
-			.join();
+		if let Some(control_thread) = self.control_thread {
+			let _ = control_thread.join();
+		}
 	}
 
 	#[inline(always)]
@@ -252,6 +378,15 @@ impl Controls {
 			.send(());
 	}
 
+	#[inline(always)]
+	/// Get a new [`Sender`] of [`signals`], that feeds into the same channel as the control thread.
+	///
+	/// [`signals`]: Signal
+	pub fn signal_sender_get(&self) -> Sender<Signal> {
+		self.signal_sender
+			.clone()
+	}
+
 	#[inline]
 	/// Try to receive a signal, by waiting for it for a set amount of time.
 	///
@@ -262,6 +397,35 @@ impl Controls {
 		self.signal_receiver
 			.try_recv()
 	}
+
+	#[inline]
+	/// Receive a signal, by waiting for it for at most the given amount of time.
+	///
+	/// This function is an analog to calling [`Receiver.recv_timeout`].
+	///
+	/// [`Receiver.recv_timeout`]: Receiver::recv_timeout
+	pub fn signal_await(&self, timeout: Duration) -> Result<Signal, RecvTimeoutError> {
+		self.signal_receiver
+			.recv_timeout(timeout)
+	}
+
+	#[inline]
+	/// Get the numeric entry that is being typed, which is empty if there is none.
+	pub fn entry_get(&self) -> String {
+		self.entry
+			.lock()
+			.map(|entry| entry.clone())
+			.unwrap_or_default()
+	}
+
+	#[inline(always)]
+	/// Get the 1-based number of the last finished numeric entry, which the jump [`signals`] refer to.
+	///
+	/// [`signals`]: Signal
+	pub fn target_get(&self) -> usize {
+		self.target
+			.load(Ordering::Relaxed)
+	}
 }
 
 macro_rules! pat {
@@ -270,6 +434,51 @@ macro_rules! pat {
 	}
 }
 impl Signal {
+	/// Every [`Signal`], paired with its name.
+	const NAMES: [(Self, &'static str); 23] = [
+		(Self::QueueNext, "queue-next"),
+		(Self::QueueEnd, "queue-end"),
+		(Self::TrackRestart, "track-restart"),
+		(Self::PlaylistNext, "playlist-next"),
+		(Self::PlaylistBack, "playlist-back"),
+		(Self::Exit, "exit"),
+		(Self::PlaylistReset, "playlist-reset"),
+		(Self::TrackNext, "track-next"),
+		(Self::TrackBack, "track-back"),
+		(Self::Play, "play"),
+		(Self::TrackReset, "track-reset"),
+		(Self::VolumeIncrease, "volume-increase"),
+		(Self::VolumeDecrease, "volume-decrease"),
+		(Self::Mute, "mute"),
+		(Self::VolumeReset, "volume-reset"),
+		(Self::SelectionNext, "selection-next"),
+		(Self::SelectionBack, "selection-back"),
+		(Self::SelectionPlay, "selection-play"),
+		(Self::TrackJump, "track-jump"),
+		(Self::PlaylistJump, "playlist-jump"),
+		(Self::SearchNext, "search-next"),
+		(Self::SearchBack, "search-back"),
+		(Self::SearchUpdate, "search-update"),
+	];
+
+	#[inline]
+	/// Get the [`Signal`] with the given name (e.g.: `track-next`).
+	pub fn from_name(name: &str) -> Option<Self> {
+		Self::NAMES
+			.iter()
+			.find(|(_, other)| *other == name)
+			.map(|(signal, _)| *signal)
+	}
+
+	#[inline]
+	/// Get the name of `self` (e.g.: `track-next`).
+	pub fn name(&self) -> &'static str {
+		Self::NAMES
+			.iter()
+			.find(|(signal, _)| *signal as u8 == *self as u8)
+			.map_or("", |(_, name)| name)
+	}
+
 	#[inline(always)]
 	/// Mask function that checks whether `self` is [`Next`] or [`Back`].
 	///
@@ -316,7 +525,200 @@ impl Signal {
 	/// Mask function that checks whether `self` is on the track layer.
 	pub fn is_track(&self) -> bool { pat!(self => TrackNext | TrackBack | TrackReset) }
 
+	#[inline(always)]
+	/// Mask function that checks whether `self` is a queueing instruction.
+	pub fn is_queue(&self) -> bool { pat!(self => QueueNext | QueueEnd) }
+
 	#[inline(always)]
 	/// Mask function that checks whether `self` is on the volume layer.
 	pub fn is_volume(&self) -> bool { pat!(self => VolumeIncrease | VolumeDecrease | Mute | VolumeReset) }
+
+	#[inline(always)]
+	/// Mask function that checks whether `self` moves the selection.
+	pub fn is_selection_move(&self) -> bool { pat!(self => SelectionNext | SelectionBack) }
+
+	#[inline(always)]
+	/// Mask function that checks whether `self` jumps to a numbered [`Track`] or [`Playlist`].
+	///
+	/// [`Track`]: crate::playback::Track
+	/// [`Playlist`]: crate::playback::Playlist
+	pub fn is_jump(&self) -> bool { pat!(self => TrackJump | PlaylistJump) }
+
+	#[inline(always)]
+	/// Mask function that checks whether `self` belongs to the search.
+	pub fn is_search(&self) -> bool { pat!(self => SearchNext | SearchBack | SearchUpdate) }
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// What a key press did to the numeric entry.
+enum Typed {
+	/// The key is not part of an entry, and is looked up in the key bindings.
+	Ignored,
+	/// The key was added to (or removed from) the entry.
+	Consumed,
+	/// The entry was finished, with a jump [`Signal`] and its 1-based number.
+	Finished(Signal, usize),
+	/// The search was changed (or finished), which the playback is told with the [`Signal`].
+	Searched(Signal),
+}
+
+/// Feed a key press into the numeric entry.
+///
+/// An entry is started by an unbound digit, or an unbound colon, and finished with enter:
+/// - `12`, `:12` or `:t 12`: jump to the twelfth track of the current playlist.
+/// - `:p 3`: jump to the third playlist.
+///
+/// Escape cancels the entry, as does any key that cannot be typed.
+///
+/// An unbound slash starts a search instead, see [`search_type`].
+fn entry_type(entry: &mut String, key: KeyEvent, is_bound: bool) -> Typed {
+	let is_plain = (key.modifiers - KeyModifiers::SHIFT).is_empty();
+	if entry.is_empty() {
+		return match key.code {
+			KeyCode::Char(character) if is_plain && !is_bound && (character.is_ascii_digit() || character == ':' || character == '/') => {
+				entry.push(character);
+				Typed::Consumed
+			},
+			_ => Typed::Ignored,
+		};
+	}
+	if entry.starts_with('/') {
+		return search_type(entry, key, is_plain);
+	}
+	match key.code {
+		KeyCode::Char(character) if is_plain => entry.push(character),
+		KeyCode::Backspace => {
+			entry.pop();
+		},
+		KeyCode::Enter => {
+			let finished = entry_parse(entry);
+			entry.clear();
+			return match finished {
+				Some((signal, number)) => Typed::Finished(signal, number),
+				None => Typed::Consumed,
+			};
+		},
+		KeyCode::Esc => entry.clear(),
+		_ => {
+			entry.clear();
+			return Typed::Ignored;
+		},
+	}
+	Typed::Consumed
+}
+
+/// Feed a key press into a search, which is an entry that starts with a slash.
+///
+/// Every change of the query is announced with `search-update`, tab and back-tab cycle through the matches, and enter plays the selected match.\
+/// Escape, or removing the slash, cancels the search; any key that cannot be typed ends it, but keeps the selected match.
+fn search_type(entry: &mut String, key: KeyEvent, is_plain: bool) -> Typed {
+	let signal = match key.code {
+		KeyCode::Char(character) if is_plain => {
+			entry.push(character);
+			Signal::SearchUpdate
+		},
+		KeyCode::Backspace => {
+			entry.pop();
+			Signal::SearchUpdate
+		},
+		KeyCode::Esc => {
+			entry.clear();
+			Signal::SearchUpdate
+		},
+		KeyCode::Tab => Signal::SearchNext,
+		KeyCode::BackTab => Signal::SearchBack,
+		KeyCode::Enter => {
+			entry.clear();
+			Signal::SelectionPlay
+		},
+		_ => {
+			entry.clear();
+			return Typed::Ignored;
+		},
+	};
+	Typed::Searched(signal)
+}
+
+/// Translate a finished numeric entry into a jump [`Signal`] and its 1-based number.
+fn entry_parse(entry: &str) -> Option<(Signal, usize)> {
+	let (signal, number) = match entry.strip_prefix(':') {
+		Some(command) => match command
+			.trim()
+			.split_at_checked(1)?
+		{
+			("p", number) => (Signal::PlaylistJump, number),
+			("t", number) => (Signal::TrackJump, number),
+			_ => (Signal::TrackJump, command),
+		},
+		None => (Signal::TrackJump, entry),
+	};
+	let number = number
+		.trim()
+		.parse()
+		.ok()
+		.filter(|number| *number > 0)?;
+	Some((signal, number))
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Type a whole line into a fresh entry, key by key, and finish it with enter.
+	fn entry_finish(line: &str) -> Option<(Signal, usize)> {
+		let mut entry = String::new();
+		for character in line.chars() {
+			entry_type(&mut entry, KeyEvent::new(KeyCode::Char(character), KeyModifiers::NONE), false);
+		}
+		match entry_type(&mut entry, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), false) {
+			Typed::Finished(signal, number) => Some((signal, number)),
+			_ => None,
+		}
+	}
+
+	#[test]
+	fn parse_track_jumps() {
+		assert_eq!(entry_parse("12"), Some((Signal::TrackJump, 12)));
+		assert_eq!(entry_parse(":12"), Some((Signal::TrackJump, 12)));
+		assert_eq!(entry_parse(":t 12"), Some((Signal::TrackJump, 12)));
+		assert_eq!(entry_parse(": t12 "), Some((Signal::TrackJump, 12)));
+	}
+
+	#[test]
+	fn parse_playlist_jumps() {
+		assert_eq!(entry_parse(":p 3"), Some((Signal::PlaylistJump, 3)));
+		assert_eq!(entry_parse(":p3"), Some((Signal::PlaylistJump, 3)));
+	}
+
+	#[test]
+	fn parse_rejected() {
+		for entry in ["", ":", "0", ":0", ":p 0", ":t", ":p", ":x 3", "12a", ":p -1", "99999999999999999999999"] {
+			assert_eq!(entry_parse(entry), None, "{entry}")
+		}
+	}
+
+	#[test]
+	fn type_entries() {
+		assert_eq!(entry_finish("12"), Some((Signal::TrackJump, 12)));
+		assert_eq!(entry_finish(":p 3"), Some((Signal::PlaylistJump, 3)));
+		assert_eq!(entry_finish("0"), None);
+		assert_eq!(entry_finish("1x"), None);
+		// NOTE: an entry has to start with a digit, or a colon, so the letter is left to the key bindings.
+		assert_eq!(entry_finish("x1"), Some((Signal::TrackJump, 1)));
+	}
+
+	#[test]
+	fn type_bound_digit() {
+		let mut entry = String::new();
+		let key = KeyEvent::new(KeyCode::Char('1'), KeyModifiers::NONE);
+		assert!(matches!(entry_type(&mut entry, key, true), Typed::Ignored));
+		assert!(entry.is_empty());
+	}
+
+	#[test]
+	fn type_cancelled() {
+		let mut entry = String::new();
+		entry_type(&mut entry, KeyEvent::new(KeyCode::Char('4'), KeyModifiers::NONE), false);
+		assert!(matches!(entry_type(&mut entry, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE), false), Typed::Consumed));
+		assert!(entry.is_empty());
+	}
 }
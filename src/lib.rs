@@ -11,13 +11,38 @@
 //! [`Playlist`]: playback::Playlist
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 use crossbeam_channel::{RecvError, RecvTimeoutError, TryRecvError};
-use rodio::{decoder::DecoderError, PlayError, StreamError};
-use std::{env::VarError, io::Error as IOError};
+use rodio::{decoder::DecoderError, source::SeekError, PlayError, StreamError};
+use std::{
+	env::VarError,
+	fmt::{self, Display, Formatter},
+	io::Error as IOError,
+};
 use toml::de::Error as TOMLError;
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[macro_export]
+/// Write a message into the log, formatted like [`format`], if its [`Level`] is logged.
+///
+/// # Basic usage:
+///
+/// ```rust
+/// # use quing::log;
+/// let path = "~/Music";
+/// log!(Debug; "expanding '{path}'");
+/// ```
+///
+/// [`Level`]: logging::Level
+macro_rules! log {
+	($level: ident; $($argument: tt)+) => {
+		$crate::logging::write($crate::logging::Level::$level, format_args!($($argument)+))
+	};
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 /// A module for handling and interacting with external devices.
 pub mod in_out;
 
+/// Changes of the player's state.
+pub mod events;
+
 /// SerDe, specifically: TOML, based structure representations of the structures of [`playback`].
 pub mod serde;
 
@@ -26,6 +51,42 @@ pub mod serde;
 /// [sources]: rodio::Source
 pub mod playback;
 
+#[cfg(unix)]
+/// A local socket to control a running instance with.
+pub mod control;
+
+#[cfg(unix)]
+/// A named pipe to control a running instance with.
+pub mod fifo;
+
+/// Remembered positions of long files.
+pub mod bookmarks;
+
+/// The global configuration file.
+pub mod config;
+
+/// Metadata, as read from audio files' tags.
+pub mod tags;
+
+/// An index of the music library.
+pub mod library;
+
+/// A log of every played file.
+pub mod history;
+
+/// The seed of the last session.
+pub mod seed;
+
+/// Logging into a file.
+pub mod logging;
+
+/// Validation of playlist files.
+pub mod check;
+
+#[cfg(feature = "tui")]
+/// A full-screen interface.
+pub mod tui;
+
 /// Implementation utilities.
 mod utilities;
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -62,16 +123,60 @@ pub enum ChannelError {
 	Disconnect,
 }
 
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[cfg_attr(
+	any(debug_assertions, feature = "traits"),
+	derive(PartialEq, Eq, PartialOrd, Ord),
+	derive(Hash)
+)]
+/// Mistakes in the configuration file.
+pub enum ConfigError {
+	/// A key chord that cannot be parsed.
+	Key(String),
+
+	/// A key chord that is bound to an unknown [`Signal`] name.
+	///
+	/// [`Signal`]: in_out::Signal
+	Signal(String, String),
+
+	/// Two differently spelled key chords that mean the same.
+	Duplicate(String, String),
+
+	/// A value outside of its allowed range, as the name of the field and a description of the range.
+	Range(&'static str, &'static str),
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[cfg_attr(
+	any(debug_assertions, feature = "traits"),
+	derive(PartialEq, Eq, PartialOrd, Ord),
+	derive(Hash)
+)]
+/// Mistakes in a library query.
+pub enum QueryError {
+	/// A field name that does not exist.
+	Field(String),
+
+	/// A numeric field, as its name and the value that is not a (comparison with a) number.
+	Number(String, String),
+
+	/// A quote that is never closed.
+	Quote,
+}
+
 #[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
 pub enum Error {
 	Io(IOError),
 	Decode(DecoderError),
 	Play(PlayError),
+	Seek(SeekError),
 	Stream(StreamError),
 	Deserialise(TOMLError),
 	Variable(VarError),
 	Vector(VectorError),
 	Channel(ChannelError),
+	Config(ConfigError),
+	Query(QueryError),
 }
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 impl From<RecvTimeoutError> for ChannelError {
@@ -120,6 +225,64 @@ impl From<TryRecvError> for ChannelError {
 	}
 }
 
+impl Display for ConfigError {
+	fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Key(chord) => write!(formatter, "unknown key chord '{chord}'"),
+			Self::Signal(chord, name) => write!(formatter, "'{chord}' is bound to the unknown signal '{name}'"),
+			Self::Duplicate(first, second) => write!(formatter, "'{first}' and '{second}' are the same key chord"),
+			Self::Range(field, range) => write!(formatter, "'{field}' must be {range}"),
+		}
+	}
+}
+
+impl Display for QueryError {
+	fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Field(field) => write!(formatter, "unknown field '{field}'"),
+			Self::Number(field, value) => write!(formatter, "'{field}' must be compared with a number, not '{value}'"),
+			Self::Quote => write!(formatter, "a quote is never closed"),
+		}
+	}
+}
+
+impl Display for VectorError {
+	fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::OutOfBounds => write!(formatter, "an index is out of bounds"),
+			Self::Empty => write!(formatter, "there is nothing to play"),
+		}
+	}
+}
+
+impl Display for ChannelError {
+	fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Timeout => write!(formatter, "a channel timed out"),
+			Self::Empty => write!(formatter, "a channel is empty"),
+			Self::Disconnect => write!(formatter, "a channel is disconnected"),
+		}
+	}
+}
+
+impl Display for Error {
+	fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Io(why) => write!(formatter, "input or output failed; '{why}'"),
+			Self::Decode(why) => write!(formatter, "a file cannot be decoded; '{why}'"),
+			Self::Play(why) => write!(formatter, "a file cannot be played; '{why}'"),
+			Self::Seek(why) => write!(formatter, "a file cannot be seeked in; '{why}'"),
+			Self::Stream(why) => write!(formatter, "the audio output cannot be opened; '{why}'"),
+			Self::Deserialise(why) => write!(formatter, "a file cannot be parsed; '{why}'"),
+			Self::Variable(why) => write!(formatter, "an environment variable cannot be read; '{why}'"),
+			Self::Vector(why) => write!(formatter, "{why}"),
+			Self::Channel(why) => write!(formatter, "{why}"),
+			Self::Config(why) => write!(formatter, "invalid configuration; {why}"),
+			Self::Query(why) => write!(formatter, "invalid query; {why}"),
+		}
+	}
+}
+
 impl From<IOError> for Error {
 	#[inline(always)]
 	fn from(inner: IOError) -> Self {
@@ -138,6 +301,12 @@ impl From<PlayError> for Error {
 		Self::Play(inner)
 	}
 }
+impl From<SeekError> for Error {
+	#[inline(always)]
+	fn from(inner: SeekError) -> Self {
+		Self::Seek(inner)
+	}
+}
 impl From<StreamError> for Error {
 	#[inline(always)]
 	fn from(inner: StreamError) -> Self {
@@ -168,3 +337,15 @@ impl From<ChannelError> for Error {
 		Self::Channel(inner)
 	}
 }
+impl From<ConfigError> for Error {
+	#[inline(always)]
+	fn from(inner: ConfigError) -> Self {
+		Self::Config(inner)
+	}
+}
+impl From<QueryError> for Error {
+	#[inline(always)]
+	fn from(inner: QueryError) -> Self {
+		Self::Query(inner)
+	}
+}
@@ -0,0 +1,552 @@
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+//! A local index of the music library, and queries over it.
+//!
+//! The index is built with `quing scan <directory>`, and kept inside of the state directory, one JSON object per file and line.\
+//! Scanning again only reads the tags of files that were changed since.
+//!
+//! A [`Query`] is a list of terms, which must all match:
+//! - `word`, or `"some words"`: contained in the title, artist, album, or path.
+//! - `artist:word`, or `artist:"some words"`: contained in the given field (`title`, `artist`, `album`, `path`).
+//! - `year:2000`, `year:>2000`, `track:<=3`: compares a number (`year`, `track`, `duration` in seconds), with `=`, `<`, `<=`, `>` or `>=`.
+//!
+//! Text is compared ignoring case.
+//!
+//! Playlist files can also be generated from the index, and the [`History`], as [`Smart`] playlists.
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+use super::{history::History, tags::Tags, utilities::state_path, Error, QueryError};
+use serde::{Deserialize, Serialize};
+use std::{
+	cmp::Reverse,
+	collections::HashMap,
+	fs::{read_dir, read_to_string, write},
+	io::ErrorKind,
+	mem::take,
+	path::{Path, PathBuf},
+	time::UNIX_EPOCH,
+};
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// The name of the index, in the state directory.
+const INDEX_NAME: &str = "library";
+
+/// The extensions of the files that are indexed.
+const EXTENSIONS: [&str; 9] = ["aac", "flac", "m4a", "mp3", "mp4", "oga", "ogg", "opus", "wav"];
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[derive(Deserialize, Serialize)]
+#[derive(Clone)]
+/// A single indexed file.
+pub struct Entry {
+	pub path: PathBuf,
+	/// When the file was last changed, in seconds since the unix epoch.
+	pub modified: u64,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub title: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub artist: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub album: Option<String>,
+	/// The number of the track on its album.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub track: Option<u32>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub year: Option<u32>,
+	/// The length of the file, in seconds.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub duration: Option<f64>,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+/// What a single [`Library::scan`] found.
+pub struct Scan {
+	/// The amount of indexed files in the directory.
+	pub count: usize,
+	/// How many of them had to be read, as they were new or changed.
+	pub read: usize,
+	/// The sub-directories that could not be read, and why.
+	pub skipped: Vec<(PathBuf, Error)>,
+}
+
+/// The index of the music library.
+pub struct Library {
+	/// Where the index is stored.
+	file_path: PathBuf,
+	/// Sorted by path.
+	entries: Vec<Entry>,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[derive(Clone, Copy)]
+/// The fields of an [`Entry`] that a [`Query`] can name.
+enum Field {
+	Title,
+	Artist,
+	Album,
+	Path,
+	Track,
+	Year,
+	Duration,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[derive(Clone, Copy)]
+/// How a number is compared.
+enum Comparison {
+	Equal,
+	Less,
+	LessEqual,
+	Greater,
+	GreaterEqual,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+/// A single term of a [`Query`].
+enum Term {
+	/// Text that is contained in the named field, or in any text field.
+	Text(Option<Field>, String),
+	/// A number that a numeric field is compared with.
+	Number(Field, Comparison, f64),
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[cfg_attr(
+	any(debug_assertions, feature = "traits"),
+	derive(PartialEq, Eq, PartialOrd, Ord),
+	derive(Hash)
+)]
+#[derive(Deserialize, Serialize)]
+#[derive(Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+/// Playlists that are generated when they are loaded, instead of listing their files.
+pub enum Smart {
+	/// The most often played files first, see [`History::play_counts_get`].
+	MostPlayed,
+	/// The most recently changed (or added) files of the index first.
+	RecentlyAdded,
+	/// The files of the index that were never played.
+	Unplayed,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+/// A parsed query, see the [module] for its syntax.
+///
+/// [module]: self
+pub struct Query(Vec<Term>);
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+impl Library {
+	#[inline(always)]
+	/// Load the index from the state directory.
+	pub fn try_new() -> Result<Self, Error> {
+		Self::try_from_path(state_path(INDEX_NAME)?)
+	}
+
+	/// Load the index from an arbitrary file.
+	///
+	/// A missing file is treated as an empty index, lines that cannot be parsed are ignored.
+	pub fn try_from_path(file_path: PathBuf) -> Result<Self, Error> {
+		let mut entries: Vec<Entry> = match read_to_string(&file_path) {
+			Ok(contents) => contents
+				.lines()
+				.filter_map(|line| serde_json::from_str(line).ok())
+				.collect(),
+			Err(error) if error.kind() == ErrorKind::NotFound => Vec::new(),
+			Err(error) => Err(error)?,
+		};
+		entries.sort_by(|one, other| one.path.cmp(&other.path));
+		Ok(Self { file_path, entries })
+	}
+
+	#[inline(always)]
+	/// Get every indexed file, sorted by path.
+	pub fn entries_get(&self) -> &[Entry] {
+		&self.entries
+	}
+
+	/// Index every audio file inside of a directory (and its sub-directories), and store the index.
+	///
+	/// Files that did not change since the last scan keep their [`Entry`], files that are gone are removed.\
+	/// Returns what was found, see [`Scan`].
+	pub fn scan(&mut self, directory: &Path) -> Result<Scan, Error> {
+		let directory = directory.canonicalize()?;
+		let mut previous: HashMap<PathBuf, Entry> = HashMap::new();
+		let mut kept = Vec::with_capacity(self.entries.len());
+		for entry in self
+			.entries
+			.drain(..)
+		{
+			if entry
+				.path
+				.starts_with(&directory)
+			{
+				previous.insert(entry.path.clone(), entry);
+			} else {
+				kept.push(entry)
+			}
+		}
+
+		let mut found = Vec::new();
+		let mut skipped = Vec::new();
+		files_collect(&directory, &mut found, &mut skipped)?;
+		let count = found.len();
+		let mut read = 0;
+		for (path, modified) in found {
+			let entry = match previous.remove(&path) {
+				Some(entry) if entry.modified == modified => entry,
+				_ => {
+					read += 1;
+					entry_read(path, modified)
+				},
+			};
+			kept.push(entry)
+		}
+		kept.sort_by(|one, other| one.path.cmp(&other.path));
+		self.entries = kept;
+		self.save()?;
+		Ok(Scan { count, read, skipped })
+	}
+
+	/// Write the index to its file.
+	pub fn save(&self) -> Result<(), Error> {
+		let contents: String = self
+			.entries
+			.iter()
+			.filter_map(|entry| serde_json::to_string(entry).ok())
+			.map(|line| line + "\n")
+			.collect();
+		write(&self.file_path, contents).map_err(Error::from)
+	}
+
+	/// Get the files of a [`Smart`] playlist, in its order.
+	///
+	/// With a [`Query`], only the matching files of the index are considered.\
+	/// Without one, [`MostPlayed`] considers every file of the [`History`] that still exists, and the others every file of the index.
+	///
+	/// [`MostPlayed`]: Smart::MostPlayed
+	pub fn smart_resolve(&self, smart: Smart, query: Option<&Query>, history: &History) -> Result<Vec<PathBuf>, Error> {
+		let counts = history.play_counts_get()?;
+		let count = |path: &Path| {
+			counts
+				.get(path)
+				.copied()
+				.unwrap_or_default()
+		};
+		let mut candidates: Vec<&Entry> = self
+			.entries
+			.iter()
+			.filter(|entry| query.is_none_or(|query| query.matches(entry)))
+			.collect();
+		let paths = match smart {
+			Smart::MostPlayed => {
+				let mut paths: Vec<PathBuf> = match query {
+					Some(_) => candidates
+						.into_iter()
+						.map(|entry| entry.path.clone())
+						.filter(|path| count(path) > 0)
+						.collect(),
+					None => counts
+						.keys()
+						.filter(|path| path.exists())
+						.cloned()
+						.collect(),
+				};
+				paths.sort_by(|one, other| {
+					count(other)
+						.cmp(&count(one))
+						.then_with(|| one.cmp(other))
+				});
+				paths
+			},
+			Smart::RecentlyAdded => {
+				candidates.sort_by_key(|entry| Reverse(entry.modified));
+				candidates
+					.into_iter()
+					.map(|entry| entry.path.clone())
+					.collect()
+			},
+			Smart::Unplayed => candidates
+				.into_iter()
+				.map(|entry| entry.path.clone())
+				.filter(|path| count(path) == 0)
+				.collect(),
+		};
+		Ok(paths)
+	}
+
+	#[inline]
+	/// Get every indexed file that matches a [`Query`], sorted by path.
+	pub fn query<'a>(&'a self, query: &'a Query) -> impl Iterator<Item = &'a Entry> + 'a {
+		self.entries
+			.iter()
+			.filter(|entry| query.matches(entry))
+	}
+}
+
+impl Query {
+	/// Parse a query, see the [module] for its syntax.
+	///
+	/// [module]: self
+	pub fn parse(text: &str) -> Result<Self, QueryError> {
+		let mut terms = Vec::new();
+		let mut characters = text
+			.chars()
+			.peekable();
+		loop {
+			while characters
+				.next_if(|character| character.is_whitespace())
+				.is_some()
+			{}
+			if characters
+				.peek()
+				.is_none()
+			{
+				break;
+			}
+			let mut key = None;
+			let mut value = String::new();
+			let mut is_quoted = false;
+			let mut was_quoted = false;
+			for character in characters.by_ref() {
+				match character {
+					'"' => {
+						is_quoted = !is_quoted;
+						was_quoted = true
+					},
+					':' if !is_quoted && !was_quoted && key.is_none() => key = Some(take(&mut value)),
+					character if character.is_whitespace() && !is_quoted => break,
+					character => value.push(character),
+				}
+			}
+			if is_quoted {
+				Err(QueryError::Quote)?
+			}
+			terms.push(Term::parse(key, value)?)
+		}
+		Ok(Self(terms))
+	}
+
+	/// Check whether an [`Entry`] matches every term.
+	pub fn matches(&self, entry: &Entry) -> bool {
+		self.0
+			.iter()
+			.all(|term| term.matches(entry))
+	}
+}
+
+impl Term {
+	/// Build a term from its (optional) field name, and its value.
+	fn parse(key: Option<String>, value: String) -> Result<Self, QueryError> {
+		let Some(key) = key else {
+			return Ok(Self::Text(None, value.to_lowercase()));
+		};
+		let field = match key
+			.to_lowercase()
+			.as_str()
+		{
+			"title" => Field::Title,
+			"artist" => Field::Artist,
+			"album" => Field::Album,
+			"path" | "file" => Field::Path,
+			"track" => Field::Track,
+			"year" => Field::Year,
+			"duration" => Field::Duration,
+			_ => return Err(QueryError::Field(key)),
+		};
+		if let Field::Title | Field::Artist | Field::Album | Field::Path = field {
+			return Ok(Self::Text(Some(field), value.to_lowercase()));
+		}
+		let (comparison, number) = [
+			(">=", Comparison::GreaterEqual),
+			("<=", Comparison::LessEqual),
+			(">", Comparison::Greater),
+			("<", Comparison::Less),
+			("=", Comparison::Equal),
+		]
+		.into_iter()
+		.find_map(|(prefix, comparison)| {
+			value
+				.strip_prefix(prefix)
+				.map(|number| (comparison, number))
+		})
+		.unwrap_or((Comparison::Equal, &value));
+		match number
+			.trim()
+			.parse()
+		{
+			Ok(number) => Ok(Self::Number(field, comparison, number)),
+			Err(_) => Err(QueryError::Number(key, value)),
+		}
+	}
+
+	/// Check whether an [`Entry`] matches the term.
+	fn matches(&self, entry: &Entry) -> bool {
+		let path = entry
+			.path
+			.to_string_lossy();
+		let text = |field: Field| match field {
+			Field::Title => entry.title.as_deref(),
+			Field::Artist => entry.artist.as_deref(),
+			Field::Album => entry.album.as_deref(),
+			_ => Some(&*path),
+		};
+		let contains = |field: Field, wanted: &str| {
+			text(field).is_some_and(|found| {
+				found
+					.to_lowercase()
+					.contains(wanted)
+			})
+		};
+		match self {
+			Self::Text(Some(field), wanted) => contains(*field, wanted),
+			Self::Text(None, wanted) => [Field::Title, Field::Artist, Field::Album, Field::Path]
+				.into_iter()
+				.any(|field| contains(field, wanted)),
+			Self::Number(field, comparison, wanted) => {
+				let found = match field {
+					Field::Track => entry.track.map(f64::from),
+					Field::Year => entry.year.map(f64::from),
+					_ => entry.duration,
+				};
+				found.is_some_and(|found| match comparison {
+					Comparison::Equal => found == *wanted,
+					Comparison::Less => found < *wanted,
+					Comparison::LessEqual => found <= *wanted,
+					Comparison::Greater => found > *wanted,
+					Comparison::GreaterEqual => found >= *wanted,
+				})
+			},
+		}
+	}
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// Collect every audio file inside of a directory, and its sub-directories, together with when it was last changed.
+///
+/// Symbolic links are not followed, so that linked directories cannot loop; sub-directories that cannot be read are skipped, and kept in `skipped`.
+fn files_collect(directory: &Path, found: &mut Vec<(PathBuf, u64)>, skipped: &mut Vec<(PathBuf, Error)>) -> Result<(), Error> {
+	for entry in read_dir(directory)? {
+		let entry = entry?;
+		let kind = entry.file_type()?;
+		let path = entry.path();
+		if kind.is_dir() {
+			// NOTE: a single unreadable directory should not keep the rest of the library from being indexed.
+			if let Err(why) = files_collect(&path, found, skipped) {
+				log!(Warn; "skipping '{}' whilst scanning; {why}", path.display());
+				skipped.push((path, why))
+			}
+			continue;
+		}
+		let is_audio = path
+			.extension()
+			.and_then(|extension| extension.to_str())
+			.is_some_and(|extension| EXTENSIONS.contains(&extension.to_lowercase().as_str()));
+		if !kind.is_file() || !is_audio {
+			continue;
+		}
+		let modified = entry
+			.metadata()?
+			.modified()?
+			.duration_since(UNIX_EPOCH)
+			.map_or(0, |since| since.as_secs());
+		found.push((path, modified))
+	}
+	Ok(())
+}
+
+/// Read the [`Tags`] of a file into a new [`Entry`].
+fn entry_read(path: PathBuf, modified: u64) -> Entry {
+	let tags = Tags::read(&path);
+	Entry {
+		title: tags
+			.title_get()
+			.map(String::from),
+		artist: tags
+			.artist_get()
+			.map(String::from),
+		album: tags
+			.album_get()
+			.map(String::from),
+		track: tags.track_number_get(),
+		year: tags.year_get(),
+		duration: tags
+			.duration_get()
+			.map(|duration| duration.as_secs_f64()),
+		path,
+		modified,
+	}
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// An [`Entry`] of a well tagged file.
+	fn entry_get() -> Entry {
+		Entry {
+			path: PathBuf::from("/music/Queen/A Night at the Opera/11 Bohemian Rhapsody.flac"),
+			modified: 0,
+			title: Some(String::from("Bohemian Rhapsody")),
+			artist: Some(String::from("Queen")),
+			album: Some(String::from("A Night at the Opera")),
+			track: Some(11),
+			year: Some(1975),
+			duration: Some(354.0),
+		}
+	}
+
+	/// Parse a query, and match it against [`entry_get`].
+	fn is_match(text: &str) -> bool {
+		Query::parse(text)
+			.unwrap()
+			.matches(&entry_get())
+	}
+
+	#[test]
+	fn parse_bare_words() {
+		let Query(terms) = Query::parse("  Queen   Rhapsody ").unwrap();
+		assert!(matches!(&terms[..], [Term::Text(None, one), Term::Text(None, other)] if one == "queen" && other == "rhapsody"));
+		assert!(Query::parse("")
+			.unwrap()
+			.0
+			.is_empty());
+	}
+
+	#[test]
+	fn parse_fields() {
+		let Query(terms) = Query::parse("artist:\"Freddie Mercury\" title:love").unwrap();
+		assert!(matches!(&terms[..], [Term::Text(Some(Field::Artist), one), Term::Text(Some(Field::Title), other)] if one == "freddie mercury" && other == "love"));
+		let Query(terms) = Query::parse("\"a night\"").unwrap();
+		assert!(matches!(&terms[..], [Term::Text(None, words)] if words == "a night"));
+	}
+
+	#[test]
+	fn parse_numbers() {
+		let Query(terms) = Query::parse("year:>2000 track:<=3 duration:=120 year:1975").unwrap();
+		assert!(matches!(
+			&terms[..],
+			[
+				Term::Number(Field::Year, Comparison::Greater, 2000.0),
+				Term::Number(Field::Track, Comparison::LessEqual, 3.0),
+				Term::Number(Field::Duration, Comparison::Equal, 120.0),
+				Term::Number(Field::Year, Comparison::Equal, 1975.0),
+			]
+		));
+	}
+
+	#[test]
+	fn parse_malformed() {
+		assert_eq!(Query::parse("genre:rock").err(), Some(QueryError::Field(String::from("genre"))));
+		assert_eq!(Query::parse("year:soon").err(), Some(QueryError::Number(String::from("year"), String::from("soon"))));
+		assert_eq!(Query::parse("year:>").err(), Some(QueryError::Number(String::from("year"), String::from(">"))));
+		assert_eq!(Query::parse("artist:\"queen").err(), Some(QueryError::Quote));
+	}
+
+	#[test]
+	fn query_matches() {
+		assert!(is_match("queen"));
+		assert!(is_match("QUEEN rhapsody"));
+		assert!(is_match("album:\"night at\""));
+		assert!(is_match("path:opera"));
+		assert!(is_match("year:<1980 track:>=11"));
+		assert!(!is_match("queen year:>1980"));
+		assert!(!is_match("artist:rhapsody"));
+		assert!(!is_match("abba"));
+	}
+}
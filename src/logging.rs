@@ -0,0 +1,163 @@
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+//! Logging into a file inside of the state directory.
+//!
+//! Nothing is logged until a [`Level`] is chosen (see [`start`]), as printing to the terminal would corrupt raw-mode output.\
+//! Every line is `seconds<TAB>level<TAB>message`, where seconds are counted since the unix epoch.\
+//! Once the log grows past [`ROTATION_SIZE`], it is moved to `log.old` (replacing the previous one), and a new log is begun.
+//!
+//! Messages are usually written with the [`log`] macro.
+//!
+//! [`log`]: crate::log
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+use super::{utilities::state_path, Error};
+use std::{
+	fmt::Arguments,
+	fs::{rename, File, OpenOptions},
+	io::Write,
+	path::PathBuf,
+	sync::{Mutex, OnceLock},
+	time::{SystemTime, UNIX_EPOCH},
+};
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// The name of the log, in the state directory.
+const FILE_NAME: &str = "log";
+
+/// The name of the rotated log, in the state directory.
+const OLD_FILE_NAME: &str = "log.old";
+
+/// The size in bytes, after which the log is rotated.
+pub const ROTATION_SIZE: u64 = 1 << 20;
+
+/// The logger of the process, if logging was started.
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[cfg_attr(
+	any(debug_assertions, feature = "traits"),
+	derive(PartialEq, Eq, PartialOrd, Ord),
+	derive(Hash)
+)]
+#[derive(Clone, Copy)]
+#[repr(u8)]
+/// How important a message is.
+///
+/// Choosing a level logs the messages of that level, and of every more important one.
+pub enum Level {
+	/// Something failed, and was given up on.
+	Error,
+	/// Something failed, but was worked around.
+	Warn,
+	/// Changes of the player's state (e.g.: which [`Track`] plays).
+	///
+	/// [`Track`]: crate::playback::Track
+	Info,
+	/// Everything else (e.g.: every handled [`Signal`], or expanded path).
+	///
+	/// [`Signal`]: crate::in_out::Signal
+	Debug,
+}
+
+/// The open log, and the level that it was started with.
+struct Logger {
+	level: Level,
+	file_path: PathBuf,
+	/// The log, and its size in bytes.
+	file: Mutex<(File, u64)>,
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// Start logging messages of the given [`Level`] (and more important ones) into the log, inside of the state directory.
+///
+/// Starting more than once keeps the first level.
+pub fn start(level: Level) -> Result<(), Error> {
+	let file_path = state_path(FILE_NAME)?;
+	let file = OpenOptions::new()
+		.create(true)
+		.append(true)
+		.open(&file_path)?;
+	let size = file
+		.metadata()?
+		.len();
+	let _ = LOGGER.set(Logger {
+		level,
+		file_path,
+		file: Mutex::new((file, size)),
+	});
+	Ok(())
+}
+
+#[inline]
+/// Whether or not messages of the given [`Level`] are logged.
+pub fn is_logged(level: Level) -> bool {
+	LOGGER
+		.get()
+		.is_some_and(|logger| level as u8 <= logger.level as u8)
+}
+
+/// Write a message into the log, if its [`Level`] is logged.
+///
+/// Failing to write is ignored, as the log must never stop the playback.
+pub fn write(level: Level, message: Arguments) {
+	let Some(logger) = LOGGER
+		.get()
+		.filter(|_| is_logged(level))
+	else {
+		return;
+	};
+	let Ok(mut guard) = logger
+		.file
+		.lock()
+	else {
+		return;
+	};
+	let (file, size) = &mut *guard;
+	if *size >= ROTATION_SIZE {
+		let rotated = logger
+			.file_path
+			.with_file_name(OLD_FILE_NAME);
+		let reopened = rename(&logger.file_path, rotated).and_then(|_| {
+			OpenOptions::new()
+				.create(true)
+				.append(true)
+				.open(&logger.file_path)
+		});
+		if let Ok(reopened) = reopened {
+			*file = reopened;
+			*size = 0
+		}
+	}
+	let time = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |since| since.as_secs());
+	let line = format!("{time}\t{}\t{message}\n", level.name());
+	if file
+		.write_all(line.as_bytes())
+		.is_ok()
+	{
+		*size += line.len() as u64
+	}
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+impl Level {
+	#[inline]
+	/// Get the [`Level`] with the given name.
+	pub fn from_name(name: &str) -> Option<Self> {
+		match name {
+			"error" => Some(Self::Error),
+			"warn" => Some(Self::Warn),
+			"info" => Some(Self::Info),
+			"debug" => Some(Self::Debug),
+			_ => None,
+		}
+	}
+
+	#[inline]
+	/// Get the name of `self`.
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::Error => "error",
+			Self::Warn => "warn",
+			Self::Info => "info",
+			Self::Debug => "debug",
+		}
+	}
+}
@@ -1,14 +1,42 @@
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 use crossterm::{
 	cursor::{Hide, Show},
+	event::{DisableMouseCapture, EnableMouseCapture},
 	execute,
 	terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode_enabled},
 };
+use fastrand::Rng;
+#[cfg(feature = "tui")]
+use quing::tui::Tui;
+#[cfg(unix)]
 use quing::{
-	playback::{ControlFlow, Playhandle, Playlist},
+	control::{pid_path, request, subscribe},
+	fifo::Fifo,
+};
+use quing::{
+	bookmarks::Bookmarks,
+	check::Report,
+	config::Config,
+	history::History,
+	in_out::IOHandle,
+	library::{Library, Query, Scan},
+	log,
+	logging::{self, Level},
+	playback::{ControlFlow, ErrorPolicy, Playhandle, Playlist, Summary},
+	seed,
 	serde::SerDePlaylist,
 	Error, VectorError,
 };
+#[cfg(unix)]
+use serde_json::{from_str, Value};
+#[cfg(unix)]
+use std::{
+	env::{current_exe, var_os},
+	fs::{remove_file, write},
+	io::Error as IoError,
+	os::unix::process::CommandExt,
+	process::{id, Command, Stdio},
+};
 use std::{
 	convert::identity,
 	env::args,
@@ -16,7 +44,9 @@ use std::{
 	iter::Peekable,
 	ops::{Deref, DerefMut},
 	panic::{self, PanicInfo},
+	path::{Path, PathBuf},
 	process::ExitCode,
+	time::Duration,
 };
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 create_flags! {
@@ -64,28 +94,16 @@ create_flags! {
 	/// The length of the set that contain all possible single character flags.
 	LENGTH = 26
 }
-///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
-#[macro_export]
-/// A macro for general interaction with Standard-Out.
-///
-/// This macro is, in a general sense, just a fancier [`println`] macro, which also is more tailored towards [raw-mode].
-///
-/// [raw-mode]: crossterm::terminal#raw-mode
-macro_rules! log {
-	($($value: expr),*; $message: literal $($why: ident)+ $(; $($retaliation: tt)+)?) => {
-		{
-			print!(
-				concat!("\rError whilst ", $message, ';')
-				$(, $value)*
-			);
-			$(print!(" '{}'", $why);)+
-			print!("\n");
-			$($($retaliation)+)?
-		}
-	};
 
-}
+/// Long options (e.g.: `--seed 42`) that are followed by a value.
+///
+/// Like [`Flags`], they must be passed in before the playlist files.
+const VALUED_OPTIONS: [&str; 4] = ["seed", "config", "log-level", "on-error"];
 
+#[cfg(unix)]
+/// The environment variable that marks a process as the detached background instance.
+const DETACHED: &str = "QUING_DETACHED";
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 #[macro_export]
 macro_rules! count {
 	($thing: expr) => { 1 };
@@ -137,7 +155,122 @@ fn flag_check(symbol: &char) -> bool {
 	symbol.is_ascii_alphabetic() && symbol.is_ascii_lowercase()
 }
 
-fn run(arguments: impl Iterator<Item = String>, flags: Flags) -> Result<(), Error> {
+/// Remove a long option and its value from the leading program arguments.
+///
+/// Both the `--name value` and the `--name=value` form are accepted.
+fn option_take(arguments: &mut Vec<String>, name: &str) -> Option<String> {
+	let mut index = 0;
+	while let Some(argument) = arguments.get(index) {
+		let Some(option) = argument.strip_prefix("--") else {
+			if argument.starts_with('-') {
+				index += 1;
+				continue;
+			}
+			break;
+		};
+		match option.split_once('=') {
+			Some((key, value)) if key == name => {
+				let value = String::from(value);
+				arguments.remove(index);
+				return Some(value);
+			},
+			None if option == name => {
+				arguments.remove(index);
+				return (index < arguments.len()).then(|| arguments.remove(index));
+			},
+			Some(_) => index += 1,
+			None if VALUED_OPTIONS.contains(&option) => index += 2,
+			None => index += 1,
+		}
+	}
+	None
+}
+
+/// Remove a long option without a value from the leading program arguments.
+///
+/// Returns whether the option was present.
+fn switch_take(arguments: &mut Vec<String>, name: &str) -> bool {
+	let mut index = 0;
+	while let Some(argument) = arguments.get(index) {
+		match argument.strip_prefix("--") {
+			Some(option) if option == name => {
+				arguments.remove(index);
+				return true;
+			},
+			Some(option) if VALUED_OPTIONS.contains(&option) => index += 2,
+			Some(_) => index += 1,
+			None if argument.starts_with('-') => index += 1,
+			None => break,
+		}
+	}
+	false
+}
+
+/// Look an argument up in the library directories, if it is neither found in the working directory, nor starts with a `/`, `~` or `$`.
+///
+/// The argument is returned unchanged if no library directory contains it.
+fn library_resolve(argument: String, library: &[PathBuf]) -> String {
+	if argument.starts_with(['/', '~', '$'])
+		|| Path::new(&argument).exists()
+	{
+		return argument;
+	}
+	library
+		.iter()
+		.map(|root| root.join(&argument))
+		.find(|path| path.exists())
+		.and_then(|path| path
+			.to_str()
+			.map(String::from))
+		.unwrap_or(argument)
+}
+
+#[cfg(unix)]
+/// Start a copy of the program in the background, without a terminal, and return.
+///
+/// The copy is marked through the [`DETACHED`] environment variable, and is controlled through the control socket.\
+/// It runs in a new session, so that it neither has a controlling terminal, nor is hung up when the terminal closes.
+fn detach(arguments: Vec<String>, seed: u64) -> ExitCode {
+	let spawned = current_exe().and_then(|executable| {
+		let mut command = Command::new(executable);
+		command
+			.arg("--seed")
+			.arg(seed.to_string())
+			.args(arguments)
+			.env(DETACHED, "1")
+			.stdin(Stdio::null())
+			.stdout(Stdio::null())
+			.stderr(Stdio::null());
+		// SAFETY: `setsid` is async-signal-safe, and nothing else runs between forking and executing.
+		unsafe {
+			command.pre_exec(|| match libc::setsid() {
+				-1 => Err(IoError::last_os_error()),
+				_ => Ok(()),
+			})
+		};
+		command.spawn()
+	});
+	match spawned {
+		Ok(child) => {
+			println!("Playing in the background, as process {}, with seed {seed}.", child.id());
+			0.into()
+		},
+		Err(why) => {
+			println!("Error whilst starting in the background; {why}");
+			1.into()
+		},
+	}
+}
+
+fn run(
+	arguments: impl Iterator<Item = String>,
+	flags: Flags,
+	seed: u64,
+	config: Config,
+	error_policy: Option<ErrorPolicy>,
+	is_detached: bool,
+	should_read_fifo: bool,
+) -> Result<Summary, Error> {
 	let new_hook = |info: &PanicInfo| unsafe {
 		let payload = info.payload();
 		let panic = payload
@@ -155,6 +288,7 @@ fn run(arguments: impl Iterator<Item = String>, flags: Flags) -> Result<(), Erro
 			.get(1)
 			.unwrap_or(&"NO_DISPLAYABLE_INFORMATION");
 		println!("\rAn error occurred whilst attempting to {message}; '{reason}'");
+		let _ = execute!(stdout(), DisableMouseCapture);
 		let _ = disable_raw_mode();
 	};
 	panic::set_hook(Box::new(new_hook));
@@ -178,28 +312,343 @@ fn run(arguments: impl Iterator<Item = String>, flags: Flags) -> Result<(), Erro
 	if flags.should_flatten() {
 		lists = vec![SerDePlaylist::flatten(lists)?];
 	}
+	// NOTE: the default is applied after flattening, so that an explicit setting of any list wins the merge; smart lists keep their order.
+	if let Some(shuffle) = config.shuffle_get() {
+		for list in lists
+			.iter_mut()
+			.filter(|list| list.vary_get().is_none() && !list.is_smart())
+		{
+			list.vary_raw_set(shuffle)
+		}
+	}
+	let mut generator = Rng::with_seed(seed);
+	for list in lists.iter_mut() {
+		let derived = generator.u64(..);
+		if list
+			.seed_get()
+			.is_none()
+		{
+			list.seed_set(derived)
+		}
+	}
+	if !flags.should_not_shuffle() {
+		println!("\rShuffling with seed {seed}.")
+	}
+	log!(Info; "starting with seed {seed}");
+	// NOTE: failing to store the seed only keeps the session from being replayed with `--seed last`.
+	let _ = seed::store(seed);
 	let streams = lists
 		.into_iter()
 		.map(Playlist::try_from)
 		.collect::<Result<Vec<Playlist>, Error>>()?;
 
-	let mut player = Playhandle::try_from(streams)?;
+	#[cfg(unix)]
+	let mut player = if is_detached {
+		write(pid_path()?, format!("{}\n", id()))?;
+		Playhandle::raw_parts_from(IOHandle::try_new_headless()?, Bookmarks::try_new()?, streams)
+	} else {
+		Playhandle::raw_parts_from(IOHandle::try_with_keys(config.keys_get().clone())?, Bookmarks::try_new()?, streams)
+	};
+	#[cfg(not(unix))]
+	let mut player = Playhandle::raw_parts_from(IOHandle::try_with_keys(config.keys_get().clone())?, Bookmarks::try_new()?, streams);
+	if let Some(volume) = config.volume_get() {
+		player.volume_default_set(volume)
+	}
+	if let Some(step) = config.step_get() {
+		player.step_set(step)
+	}
+	player.history_set(History::try_new()?);
+	if let Some(policy) = error_policy.or(config.error_policy_get()) {
+		player.error_policy_set(policy)
+	}
+	if let Some(format) = config
+		.status_get()
+		.filter(|_| !is_detached)
+	{
+		player.status_format_set(format)
+	}
+	#[cfg(feature = "tui")]
+	if !is_detached && is_raw_mode_enabled().is_ok_and(identity) {
+		player.tui_set(Tui::try_new()?)
+	}
+	#[cfg(unix)]
+	let _fifo = if should_read_fifo {
+		Some(Fifo::try_new(
+			player
+				.io_handle_get()
+				.controls_get()
+				.signal_sender_get(),
+			player.step_get(),
+		)?)
+	} else {
+		None
+	};
+	#[cfg(not(unix))]
+	let _ = should_read_fifo;
 	match player.all_playlists_play(!flags.should_not_shuffle())? {
-		ControlFlow::Break => return Ok(()),
+		ControlFlow::Break | ControlFlow::Default => {},
 		ControlFlow::Skip | ControlFlow::SkipSkip => unimplemented!(), // NOTE(by: @OST-Gh): see playback.rs Playhandle::all_streams_play match
-		ControlFlow::Default => {},
 	};
 
+	let summary = player.summary_get();
 	player.io_handle_take()
 		.controls_take()
 		.cleanly_exit();
-	Ok(())
+	Ok(summary)
+}
+
+/// Print what happened during a session.
+fn summary_print(summary: &Summary, seed: u64) {
+	println!(
+		"\rPlayed {} tracks, skipped {}, listened for {}.",
+		summary.played,
+		summary.skipped,
+		fmt_listened(summary.listened)
+	);
+	if let Some((path, position)) = &summary.last {
+		println!("Last track: {}, at {}.", path.display(), fmt_listened(*position))
+	}
+	if !summary.broken.is_empty() {
+		println!("Could not play {} tracks:", summary.broken.len());
+		for (path, why) in &summary.broken {
+			println!("\t{}; {why}", path.display())
+		}
+	}
+	println!("Seed: {seed}.")
+}
+
+#[cfg(unix)]
+/// Send a command to a running instance, through its control socket, and print the answer.
+fn control(arguments: &[String]) -> ExitCode {
+	if arguments.is_empty() {
+		println!("No command given.");
+		return 1.into();
+	}
+	if arguments == ["subscribe"] {
+		return match subscribe() {
+			Ok(events) => {
+				for event in events {
+					println!("{event}")
+				}
+				0.into()
+			},
+			Err(why) => {
+				println!("Error whilst connecting to a running instance; {why}");
+				1.into()
+			},
+		};
+	}
+	match request(&arguments.join(" ")) {
+		Ok(answer) => {
+			print!("{answer}");
+			let is_ok = from_str::<Value>(&answer).is_ok_and(|answer| answer["ok"] == Value::Bool(true));
+			u8::from(!is_ok).into()
+		},
+		Err(why) => {
+			println!("Error whilst connecting to a running instance; {why}");
+			1.into()
+		},
+	}
+}
+
+/// Index the given directories, or the library directories of the configuration file, into the library.
+fn scan(arguments: &[String]) -> ExitCode {
+	let directories = if arguments.is_empty() {
+		match Config::try_new() {
+			Ok(config) => config
+				.library_get()
+				.to_vec(),
+			Err(why) => {
+				println!("Error whilst loading the configuration file; {why}");
+				return 1.into();
+			},
+		}
+	} else {
+		arguments
+			.iter()
+			.map(PathBuf::from)
+			.collect()
+	};
+	if directories.is_empty() {
+		println!("No directory given, and no library directory configured.");
+		return 1.into();
+	}
+	let mut library = match Library::try_new() {
+		Ok(library) => library,
+		Err(why) => {
+			println!("Error whilst loading the library index; {why}");
+			return 1.into();
+		},
+	};
+	for directory in directories {
+		match library.scan(&directory) {
+			Ok(Scan { count, read, skipped }) => {
+				println!("Indexed {count} files in {} ({read} read anew).", directory.display());
+				for (path, why) in skipped {
+					println!("Skipped {}; {why}", path.display())
+				}
+			},
+			Err(why) => {
+				println!("Error whilst scanning {}; {why}", directory.display());
+				return 1.into();
+			},
+		}
+	}
+	0.into()
+}
+
+/// Print the paths of every indexed file that matches a query.
+///
+/// The output can be piped back into the program, to play the matches.
+fn query(arguments: &[String]) -> ExitCode {
+	let query = match Query::parse(&arguments.join(" ")) {
+		Ok(query) => query,
+		Err(why) => {
+			println!("Invalid query; {why}");
+			return 1.into();
+		},
+	};
+	let library = match Library::try_new() {
+		Ok(library) => library,
+		Err(why) => {
+			println!("Error whilst loading the library index; {why}");
+			return 1.into();
+		},
+	};
+	for entry in library.query(&query) {
+		println!("{}", entry.path.display())
+	}
+	0.into()
+}
+
+/// Format a listening time as hours, minutes and seconds (e.g.: `12:03:07`).
+fn fmt_listened(listened: Duration) -> String {
+	let seconds = listened.as_secs();
+	format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+/// Print the most played files, the total listening time and the skip rates, as gathered from the history.
+///
+/// An optional argument sets how many files are listed (10 by default).
+fn stats(arguments: &[String]) -> ExitCode {
+	let count = match arguments
+		.first()
+		.map(|count| count.parse::<usize>())
+	{
+		None => 10,
+		Some(Ok(count)) => count,
+		Some(Err(_)) => {
+			println!("Invalid count '{}'.", arguments[0]);
+			return 1.into();
+		},
+	};
+	let stats = match History::try_new().and_then(|history| history.stats_get()) {
+		Ok(stats) => stats,
+		Err(why) => {
+			println!("Error whilst reading the history; {why}");
+			return 1.into();
+		},
+	};
+	println!(
+		"Listened for {} in total, {} finished, {} skipped ({:.0}% skip rate).",
+		fmt_listened(stats.listened),
+		stats.finished,
+		stats.skipped,
+		stats.skip_rate_get() * 100.0,
+	);
+	for (index, track) in stats
+		.tracks
+		.iter()
+		.take(count)
+		.enumerate()
+	{
+		println!(
+			"{:>3}. {} plays, {:.0}% skipped, {} listened: {}",
+			index + 1,
+			track.plays,
+			track.skip_rate_get() * 100.0,
+			fmt_listened(track.listened),
+			track
+				.path
+				.display()
+		)
+	}
+	0.into()
+}
+
+/// Validate playlist files, and print every mistake that is found in them.
+///
+/// Exits with a failure if anything was found.
+fn check(arguments: &[String]) -> ExitCode {
+	if arguments.is_empty() {
+		println!("No playlist file given.");
+		return 1.into();
+	}
+	let mut has_errors = false;
+	for path in arguments {
+		let report = Report::check(path);
+		if report.is_clean() {
+			println!("{path}: ok");
+			continue;
+		}
+		has_errors |= report.has_errors();
+		println!("{path}:");
+		for finding in &report.findings {
+			println!("\t{}: {}", finding.severity.name(), finding.message)
+		}
+	}
+	// NOTE: warnings are only printed, so that a playlist that plays does not fail the check.
+	(has_errors as u8).into()
+}
+
+/// Print the given playlist files, and audio files, as a single playlist file.
+///
+/// The output can be redirected into a file, to save it (e.g.: to keep the current songs of a smart playlist).
+fn export(arguments: &[String]) -> ExitCode {
+	if arguments.is_empty() {
+		println!("No playlist file given.");
+		return 1.into();
+	}
+	let contents = SerDePlaylist::try_from_paths(arguments.to_vec())
+		.and_then(|mut lists| match lists.len() {
+			1 => Ok(lists.remove(0)),
+			_ => SerDePlaylist::flatten(lists),
+		})
+		.and_then(|list| list.contents_get());
+	match contents {
+		Ok(contents) => {
+			print!("{contents}");
+			0.into()
+		},
+		Err(why) => {
+			println!("Error whilst exporting; {why}");
+			1.into()
+		},
+	}
 }
 
 fn main() -> ExitCode {
 	let mut arguments: Vec<String> = args()
 		.skip(1) // skips the executable path (e.g.: //bin/{bin-name})
 		.collect();
+	#[cfg(unix)]
+	if arguments
+		.first()
+		.is_some_and(|first| first == "ctl")
+	{
+		return control(&arguments[1..]);
+	}
+	match arguments
+		.first()
+		.map(String::as_str)
+	{
+		Some("scan") => return scan(&arguments[1..]),
+		Some("query") => return query(&arguments[1..]),
+		Some("stats") => return stats(&arguments[1..]),
+		Some("check") => return check(&arguments[1..]),
+		Some("export") => return export(&arguments[1..]),
+		_ => {},
+	}
 	let is_terminal = stdin().is_terminal();
 	if !is_terminal {
 		// NOTE(by: @OST-Gh): assume stdin is being piped
@@ -214,6 +663,81 @@ fn main() -> ExitCode {
 			.map(String::from);
 		arguments.extend(piped)
 	};
+	let seed = match option_take(&mut arguments, "seed").as_deref() {
+		Some("last") => match seed::load() {
+			Ok(seed) => seed,
+			Err(why) => {
+				println!("Error whilst loading the seed of the last session; {why}");
+				return 1.into();
+			},
+		},
+		Some(value) => match value.parse() {
+			Ok(seed) => seed,
+			Err(why) => {
+				println!("Invalid seed given; {why}");
+				return 1.into();
+			},
+		},
+		None => fastrand::u64(..),
+	};
+	#[cfg(unix)]
+	let is_detached = var_os(DETACHED).is_some();
+	#[cfg(not(unix))]
+	let is_detached = false;
+	let should_detach = switch_take(&mut arguments, "daemon");
+	#[cfg(unix)]
+	let forwarded = (should_detach && !is_detached).then(|| arguments.clone());
+	#[cfg(not(unix))]
+	if should_detach {
+		println!("Playing in the background is only supported on unix.");
+		return 1.into();
+	}
+	let should_read_fifo = switch_take(&mut arguments, "fifo");
+	if let Some(name) = option_take(&mut arguments, "log-level") {
+		let Some(level) = Level::from_name(&name) else {
+			println!("Invalid log level given; '{name}'");
+			return 1.into();
+		};
+		if let Err(why) = logging::start(level) {
+			println!("Error whilst starting the log; {why}");
+			return 1.into();
+		}
+	}
+	let error_policy = match option_take(&mut arguments, "on-error") {
+		Some(name) => match ErrorPolicy::from_name(&name) {
+			Some(policy) => Some(policy),
+			None => {
+				println!("Invalid error policy given; '{name}'");
+				return 1.into();
+			},
+		},
+		None => None,
+	};
+	let config = match option_take(&mut arguments, "config") {
+		Some(path) => Config::try_from_path(path),
+		None => Config::try_new(),
+	};
+	let config = match config {
+		Ok(config) => config,
+		Err(Error::Config(why)) => {
+			println!("Invalid configuration file; {why}");
+			return 1.into();
+		},
+		Err(why) => {
+			println!("Error whilst loading the configuration file; {why}");
+			return 1.into();
+		},
+	};
+	if let Some(defaults) = config.flags_get() {
+		if let Some(symbol) = defaults
+			.chars()
+			.find(|symbol| !Flags::INUSE_IDENTIFIERS.contains(symbol))
+		{
+			println!("Invalid configuration file; unknown flag '{symbol}'");
+			return 1.into();
+		}
+		arguments.insert(0, format!("-{defaults}"))
+	}
 	let (flags, mut files) = Flags::separate_from(arguments);
 
 	// NOTE(by: @OST-Gh): for convenience.
@@ -236,18 +760,38 @@ fn main() -> ExitCode {
 		return 1.into();
 	}
 
+	#[cfg(unix)]
+	if let Some(arguments) = forwarded {
+		return detach(arguments, seed);
+	}
+
 	if !flags.should_not_enter_raw()
 		&& is_terminal && !is_raw_mode_enabled().is_ok_and(identity)
 	{
 		let _ = enable_raw_mode();
 		let _ = execute!(stdout(), Hide);
+		if config.mouse_is() {
+			let _ = execute!(stdout(), EnableMouseCapture);
+		}
 	}
 
-	let result = run(files, flags);
-	let _ = execute!(stdout(), Show);
+	let library = config
+		.library_get()
+		.to_vec();
+	let files = files.map(|file| library_resolve(file, &library));
+	let result = run(files, flags, seed, config, error_policy, is_detached, should_read_fifo);
+	let _ = execute!(stdout(), Show, DisableMouseCapture);
 	let _ = disable_raw_mode();
-	if let Err(error) = result {
-		println!("{error}")
+	#[cfg(unix)]
+	if is_detached {
+		let _ = pid_path().map(remove_file);
+	}
+	match result {
+		Ok(summary) => summary_print(&summary, seed),
+		Err(error) => {
+			log!(Error; "{error}");
+			println!("{error}")
+		},
 	}
 	0.into()
 }
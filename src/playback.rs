@@ -6,23 +6,42 @@
 //!
 //! [`Cells`]: std::cell::Cell
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[cfg(unix)]
+use super::control::Status;
+#[cfg(feature = "tui")]
+use super::tui::Tui;
 use super::{
+	bookmarks::{Bookmarks, THRESHOLD},
+	history::{History, RecordKind},
+	events::Event,
 	in_out::{IOHandle, Signal},
-	serde::{SerDePlaylist, SerDeTrack},
-	utilities::{clear, fmt_path},
+	serde::{SerDePlaylist, SerDeTrack, SerDeVary},
+	tags::Tags,
+	utilities::{clear, fmt_duration, fmt_path, fuzzy_score, status_print},
 	ChannelError, Error, VectorError,
 };
-use crossbeam_channel::TryRecvError;
+use crossbeam_channel::{RecvTimeoutError, TryRecvError};
 use fastrand::Rng;
+use serde::{Deserialize, Serialize};
 use std::{
-	cell::Cell,
+	cell::{Cell, OnceCell},
+	collections::VecDeque,
 	fs::File,
 	io::{Read, Seek},
-	path::PathBuf,
+	path::{Path, PathBuf},
 	time::{Duration, Instant},
 };
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// The default amount by which the volume changes per volume [`Signal`].
 const STEP: f32 = 0.025;
+
+/// The minimum amount of played [`Tracks`] that a [`Playlist`] in radio mode remembers.
+///
+/// [`Tracks`]: Track
+const RADIO_HISTORY: usize = 32;
+
+/// The minimum time between two publications of the player's state.
+const STATUS_INTERVAL: Duration = Duration::from_millis(100);
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 /// A collection of [`Tracks`].
 ///
@@ -37,22 +56,40 @@ pub struct Playlist {
 	/// [`streams`]: Self#field.streams
 	track_map: Cell<Vec<usize>>,
 
-	shuffle: bool,
+	/// How the [`Playlist`] is shuffled, [`None`] if it must not be.
+	shuffle: Option<Strategy>,
+	/// The seed that the [`generator`] started out with.
+	///
+	/// [`generator`]: Self#field.generator
+	seed: u64,
+	generator: Cell<Rng>,
 
-	/// Maximum pointer offset.
+	/// If set, [`Tracks`] are drawn at random, indefinitely, whilst avoiding the given amount of last played ones.
 	///
-	/// Equates to [`len`].
+	/// [`Tracks`]: Track
+	radio: Option<usize>,
+	/// Track-pointers of the last played [`Tracks`], in radio mode.
 	///
-	/// [`len`]: Vec::len
-	length: usize,
+	/// [`Tracks`]: Track
+	history: Cell<Vec<usize>>,
+
 	tracks: Vec<Track>,
 	repeats: Cell<isize>,
 }
 
+#[derive(Clone)]
 /// A byte stream.
 pub struct Track {
 	file_path: PathBuf,
 	repeats: Cell<isize>,
+	/// Relative chance of being picked early by the [weighted] shuffle.
+	///
+	/// [weighted]: Strategy::Weighted
+	weight: u32,
+	/// Read once they are first asked for.
+	tags: OnceCell<Tags>,
+	/// Set in the playlist file, laid over the [`Tags`] read from the file.
+	overrides: Tags,
 }
 
 /// The player's state.
@@ -72,17 +109,166 @@ pub struct Playhandle {
 
 	playlists: Vec<Playlist>,
 
+	/// [`Tracks`] that are played before the next one of the current [`Playlist`].
+	///
+	/// [`Tracks`]: Track
+	queue: Cell<VecDeque<Track>>,
+	/// The [`Track`] that queueing instructions act on, as a pair of playlist-pointer and index into the [`Playlist`]'s unmapped [`Tracks`].
+	///
+	/// [`Tracks`]: Track
+	selection: Cell<Option<(usize, usize)>>,
+	/// A [`Track`] to continue with, once its [`Playlist`] plays, as a pair like the selection.
+	jump: Cell<Option<(usize, usize)>>,
+	/// Whether the last [`Track`] was left by skipping back, which makes radio mode replay the previous one.
+	skipped_back: Cell<bool>,
+	/// The matches of the last search, best first, as pairs like the selection; and which one of them is selected.
+	search: Cell<(Vec<(usize, usize)>, usize)>,
+
 	/// Global volume.
 	volume: Cell<f32>,
+	/// The volume that the volume is reset to.
+	volume_default: f32,
+	/// The amount by which the volume changes per volume [`Signal`].
+	step: f32,
 	paused: Cell<bool>,
 	//  1.0 + 2.0 * -1.0 = -1.0
 	// -1.0 + 2.0 *  1.0 =  1.0
 	io_handle: IOHandle,
+
+	/// Positions of interrupted long files.
+	bookmarks: Bookmarks,
+	/// Where finished and skipped [`Tracks`] are recorded, if anywhere.
+	///
+	/// [`Tracks`]: Track
+	history: Option<History>,
+	/// What happened during this session, see [`Summary`].
+	summary: Cell<Summary>,
+	/// What is done with [`Tracks`] that cannot be played.
+	///
+	/// [`Tracks`]: Track
+	error_policy: ErrorPolicy,
+	/// The amount of [`Tracks`] in a row that could not be played, since the last one that could.
+	///
+	/// [`Tracks`]: Track
+	broken_streak: Cell<usize>,
+	/// A message that is shown in place of an empty entry (e.g.: a report of a [`Track`] that cannot be played), until the next [`Track`] finishes.
+	notice: Cell<String>,
+	/// When the player's state was last published.
+	published: Cell<Option<Instant>>,
+	/// Whether a numeric entry was printed in place of a missing status line, and has to be cleared again.
+	entry_shown: Cell<bool>,
+	/// The format of the status line, [`None`] if none is printed.
+	status_format: Option<Box<str>>,
+	#[cfg(feature = "tui")]
+	/// The full-screen interface, which replaces the status line.
+	tui: Cell<Option<Tui>>,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[cfg_attr(
+	any(debug_assertions, feature = "traits"),
+	derive(PartialEq, Eq, PartialOrd, Ord),
+	derive(Hash)
+)]
+#[derive(Deserialize)]
+#[derive(Clone, Copy, Default)]
+#[serde(rename_all = "kebab-case")]
+/// What is done with a [`Track`] that cannot be played (e.g.: because its file is missing, or cannot be decoded).
+///
+/// Either way, the [`Track`] is logged, and kept in the [`Summary`].\
+/// Once as many [`Tracks`] in a row could not be played as the [`Playlist`] holds, the whole [`Playlist`] is skipped, so that a broken [`Playlist`] that repeats (or is a radio) does not loop forever.
+///
+/// [`Tracks`]: Track
+pub enum ErrorPolicy {
+	/// Report the [`Track`], and continue with the next one.
+	#[default]
+	Skip,
+	/// Stop playing, and exit with the error.
+	Abort,
+	/// Report the [`Track`], and wait for a [`Signal`]: exiting stops playing, and skipping to the next [`Track`] or [`Playlist`] continues with it.
+	///
+	/// The volume can still be changed whilst waiting, other [`Signals`] are ignored.
+	///
+	/// [`Signals`]: Signal
+	Prompt,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[derive(Clone, Default)]
+/// What happened during a session, gathered by a [`Playhandle`] for printing upon exiting.
+pub struct Summary {
+	/// The amount of [`Tracks`] that were played to their end.
+	///
+	/// [`Tracks`]: Track
+	pub played: usize,
+	/// The amount of [`Tracks`] that were skipped before their end, not counting the one that was quit.
+	///
+	/// [`Tracks`]: Track
+	pub skipped: usize,
+	/// The time that was actually listened to, in total.
+	pub listened: Duration,
+	/// The file of the last [`Track`], and the position that it was left at.
+	pub last: Option<(PathBuf, Duration)>,
+	/// The files of the [`Tracks`] that could not be played, and why.
+	///
+	/// [`Tracks`]: Track
+	pub broken: Vec<(PathBuf, String)>,
 }
 
 // pub struct Player {
 // }
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[cfg_attr(
+	any(debug_assertions, feature = "traits"),
+	derive(PartialEq, Eq, PartialOrd, Ord),
+	derive(Hash)
+)]
+#[derive(Deserialize, Serialize)]
+#[derive(Clone, Copy, Default)]
+#[serde(rename_all = "kebab-case")]
+/// The ways in which a [`Playlist`] can be shuffled.
+///
+/// The directory that a [`Track`]'s file lies in is treated as its album.
+pub enum Strategy {
+	/// Every order is equally likely.
+	#[default]
+	Plain,
+	/// Shuffle the albums, but keep the order of the [`Tracks`] inside of each album.
+	///
+	/// [`Tracks`]: Track
+	Album,
+	/// Like [`Plain`], but avoid playing [`Tracks`] of the same album back-to-back.
+	///
+	/// [`Plain`]: Self::Plain
+	/// [`Tracks`]: Track
+	Spread,
+	/// [`Tracks`] with a higher weight are more likely to be played early.
+	///
+	/// [`Tracks`]: Track
+	Weighted,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[cfg_attr(
+	any(debug_assertions, feature = "traits"),
+	derive(PartialEq, Eq, PartialOrd, Ord),
+	derive(Hash)
+)]
+#[derive(Clone, Copy)]
+/// The properties that the [`Tracks`] of a [`Playlist`] can be sorted by.
+///
+/// [`Tracks`]: Track
+pub enum SortKey {
+	Title,
+	Artist,
+	Album,
+	/// The number of the [`Track`] on its album.
+	Track,
+	/// The path of the file.
+	File,
+}
+
 #[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
 #[derive(Default)]
 /// Signals returned by some crucial functions.
@@ -133,20 +319,57 @@ impl Playlist {
 	/// Check if it's allowed to shuffle.
 	pub fn shuffle_can(&self) -> bool {
 		self.shuffle
+			.is_some()
+	}
+
+	#[inline(always)]
+	/// Get the [`Strategy`] that is used when shuffling.
+	pub fn strategy_get(&self) -> Option<Strategy> {
+		self.shuffle
+	}
+
+	#[inline(always)]
+	/// Get the seed that the shuffling is based on.
+	///
+	/// Loading the same [`SerDePlaylist`] with this seed reproduces the same order of [`Tracks`].
+	///
+	/// [`Tracks`]: Track
+	pub fn seed_get(&self) -> u64 {
+		self.seed
+	}
+
+	#[inline(always)]
+	/// Check if the [`Playlist`] is in radio mode.
+	pub fn radio_is(&self) -> bool {
+		self.radio
+			.is_some()
 	}
 
 	/// Play the entire playlist `n` times through a [`Playhandle`].
 	///
-	/// Where `n` is the repeats value.
+	/// Where `n` is the repeats value.\
+	/// In radio mode, see [`radio_play`], the [`Playlist`] instead plays until it is skipped or exited.
+	///
+	/// [`radio_play`]: Self::radio_play
 	pub fn play_through(
 		&self,
 		handle: &Playhandle,
 		should_shuffle: bool,
 	) -> Result<ControlFlow, Error> {
+		if let Some(avoid) = self.radio {
+			return self.radio_play(handle, avoid);
+		}
+		let playlist_index = handle.playlist_index_get()?;
 		while handle
 			.track_index_check()
 			.is_none()
 		{
+			if let Some(position) = handle.jump_take(playlist_index) {
+				unsafe { handle.track_index_set_unchecked(|_| position) }
+			}
+			if let Some(flow) = handle.queue_play()? {
+				return Ok(flow);
+			}
 			match unsafe {
 				self.nth_unchecked(handle.track_index_get_unchecked())
 					.play_through(handle)
@@ -175,9 +398,95 @@ impl Playlist {
 		Ok(().into())
 	}
 
+	/// Play randomly drawn [`Tracks`] through a [`Playhandle`], until the [`Playlist`] is skipped or exited.
+	///
+	/// The last `avoid` played [`Tracks`] are not drawn again, as long as there are enough others.\
+	/// Skipping back plays the previously drawn [`Track`] again.
+	///
+	/// [`Tracks`]: Track
+	pub fn radio_play(&self, handle: &Playhandle, avoid: usize) -> Result<ControlFlow, Error> {
+		let mut history = self
+			.history
+			.take();
+		let result = self.radio_run(handle, avoid, &mut history);
+		self.history
+			.set(history);
+		result
+	}
+
+	/// The loop of [`radio_play`], working on a taken history.
+	///
+	/// [`radio_play`]: Self::radio_play
+	fn radio_run(&self, handle: &Playhandle, avoid: usize, history: &mut Vec<usize>) -> Result<ControlFlow, Error> {
+		let playlist_index = handle.playlist_index_get()?;
+		let mut next = self.radio_draw(history, avoid);
+		loop {
+			if let Some(position) = handle.jump_take(playlist_index) {
+				next = position
+			}
+			if let Some(flow) = handle.queue_play()? {
+				return Ok(flow);
+			}
+			unsafe { handle.track_index_set_unchecked(|_| next) }
+			history.push(next);
+			let excess = history
+				.len()
+				.saturating_sub(avoid.max(RADIO_HISTORY));
+			history.drain(..excess);
+
+			handle.skipped_back
+				.set(false);
+			let result = unsafe { self.nth_unchecked(next) }.play_through(handle);
+			match result {
+				Ok(ControlFlow::Break) => return Ok(ControlFlow::Break),
+				Ok(ControlFlow::SkipSkip) => return Ok(ControlFlow::Skip),
+				Err(Error::Vector(VectorError::OutOfBounds)) if handle.playlists_have_ended() => {
+					handle.has_reached_entire_end
+						.set(true);
+					return Ok(().into());
+				},
+				Ok(_) | Err(Error::Vector(VectorError::OutOfBounds)) => {
+					// NOTE: a track-pointer that has run over the end means nothing in radio mode.
+					let _ = handle.playlist_has_ended();
+				},
+				Err(other) => Err(other)?,
+			}
+			if handle.playlist_index_get() != Ok(playlist_index) {
+				return Ok(ControlFlow::Skip);
+			}
+			next = if handle.skipped_back.take() && history.len() > 1 {
+				history.pop();
+				history
+					.pop()
+					.unwrap_or(next)
+			} else {
+				self.radio_draw(history, avoid)
+			};
+		}
+	}
+
+	/// Draw a random track-pointer, that is not one of the last `avoid` entries of the history.
+	fn radio_draw(&self, history: &[usize], avoid: usize) -> usize {
+		let count = self.tracks_count();
+		let recent = &history[history
+			.len()
+			.saturating_sub(avoid.min(count - 1))..];
+		let candidates: Vec<usize> = (0..count)
+			.filter(|index| !recent.contains(index))
+			.collect();
+		let mut generator = self
+			.generator
+			.take();
+		let index = candidates[generator.usize(..candidates.len())];
+		self.generator
+			.set(generator);
+		index
+	}
+
 	/// Shuffle all [`Tracks`] around.
 	///
-	/// The Shuffling works with the help of a [random number generator].
+	/// The Shuffling works with the help of a [random number generator], which is seeded once, when the [`Playlist`] is created.\
+	/// See [`Strategy`] for the different ways of shuffling.
 	///
 	/// [`Tracks`]: Track
 	/// [random number generator]: Rng
@@ -185,19 +494,85 @@ impl Playlist {
 		let mut map = self
 			.track_map
 			.take();
-		let mut generator = Rng::new();
-		generator.shuffle(&mut map);
+		let mut generator = self
+			.generator
+			.take();
+		match self
+			.shuffle
+			.unwrap_or_default()
+		{
+			Strategy::Plain => generator.shuffle(&mut map),
+			Strategy::Album => map = self.albums_shuffle(&mut generator),
+			Strategy::Spread => {
+				generator.shuffle(&mut map);
+				self.albums_spread(&mut map)
+			},
+			Strategy::Weighted => {
+				let mut keyed: Vec<(f64, usize)> = map
+					.into_iter()
+					.map(|index| {
+						// NOTE: weighted sampling without replacement (Efraimidis, Spirakis); higher keys come first.
+						let weight = unsafe { self.tracks.get_unchecked(index) }.weight;
+						(generator.f64().powf(1.0 / weight as f64), index)
+					})
+					.collect();
+				keyed.sort_by(|(left, _), (right, _)| right.total_cmp(left));
+				map = keyed
+					.into_iter()
+					.map(|(_, index)| index)
+					.collect()
+			},
+		}
+		self.track_map
+			.set(map);
+		self.generator
+			.set(generator)
+	}
 
-		for index in 0..self.length {
-			map.swap(index, generator.usize(0..=index));
-			map.swap(index, generator.usize(index..self.length));
-			// a b c; b inclusive in both random ranges
-			// b a c
-			// b c a
+	/// Create a map that holds the albums in random order, but keeps the order of the [`Tracks`] inside of each album.
+	///
+	/// [`Tracks`]: Track
+	fn albums_shuffle(&self, generator: &mut Rng) -> Vec<usize> {
+		let mut albums: Vec<(&Path, Vec<usize>)> = Vec::new();
+		for (index, track) in self
+			.tracks
+			.iter()
+			.enumerate()
+		{
+			let album = track.album_get();
+			match albums
+				.iter_mut()
+				.find(|(other, _)| *other == album)
+			{
+				Some((_, indices)) => indices.push(index),
+				None => albums.push((album, vec![index])),
+			}
 		}
+		generator.shuffle(&mut albums);
+		albums
+			.into_iter()
+			.flat_map(|(_, indices)| indices)
+			.collect()
+	}
 
-		self.track_map
-			.set(map)
+	/// Move [`Tracks`] around so that, where possible, no two neighbours share an album.
+	///
+	/// [`Tracks`]: Track
+	fn albums_spread(&self, map: &mut [usize]) {
+		let album = |index: usize| unsafe {
+			self.tracks
+				.get_unchecked(index)
+				.album_get()
+		};
+		for index in 1..map.len() {
+			let previous = album(map[index - 1]);
+			if album(map[index]) != previous {
+				continue;
+			}
+			if let Some(other) = (index + 1..map.len()).find(|&other| album(map[other]) != previous) {
+				map.swap(index, other)
+			}
+		}
 	}
 
 	#[inline(always)]
@@ -218,6 +593,24 @@ impl Playlist {
 		mapped_index
 	}
 
+	#[inline]
+	/// Get the position in the shuffled order of the [`Track`] at the given unmapped index.
+	///
+	/// This is the reverse of [`index_get`].
+	///
+	/// [`index_get`]: Self::index_get
+	pub fn position_get(&self, index: usize) -> Option<usize> {
+		let map = self
+			.track_map
+			.take();
+		let position = map
+			.iter()
+			.position(|&mapped_index| mapped_index == index);
+		self.track_map
+			.set(map);
+		position
+	}
+
 	#[inline(always)]
 	/// Get the correctly mapped index without bound checking.
 	///
@@ -310,27 +703,54 @@ impl Playlist {
 impl TryFrom<SerDePlaylist> for Playlist {
 	type Error = Error;
 
-	fn try_from(SerDePlaylist { song, time, vary }: SerDePlaylist) -> Result<Self, Error> {
-		let f = |tuple: Vec<(usize, Track)>| {
-			let (track_map, tracks): (Vec<usize>, Vec<Track>) = tuple
-				.into_iter()
-				.unzip();
+	fn try_from(
+		SerDePlaylist {
+			song,
+			query: _,
+			smart,
+			limit: _,
+			time,
+			vary,
+			seed,
+			radio,
+			sort,
+			filter,
+		}: SerDePlaylist,
+	) -> Result<Self, Error> {
+		let seed = seed.unwrap_or_else(|| fastrand::u64(..));
+		let f = |mut tracks: Vec<Track>| {
+			// NOTE: both read the tags of every track, so they are only done when asked for.
+			if let Some(filter) = &filter {
+				tracks.retain(|track| filter.matches(track.tags_get()))
+			}
+			if let Some(sort) = &sort {
+				tracks.sort_by(|one, other| sort.compare(one, other))
+			}
+			let track_map: Vec<usize> = (0..tracks.len()).collect();
 			if track_map.is_empty() {
 				Err(VectorError::Empty)?
 			}
 			Ok(Self {
-				shuffle: vary.unwrap_or(true),
+				shuffle: match vary {
+					// NOTE: the order of a smart playlist is its point, so it is only shuffled when asked for.
+					None if smart.is_some() => None,
+					None | Some(SerDeVary::Toggle(true)) => Some(Strategy::Plain),
+					Some(SerDeVary::Toggle(false)) => None,
+					Some(SerDeVary::Mode(strategy)) => Some(strategy),
+				},
+				seed,
+				generator: Cell::new(Rng::with_seed(seed)),
+				radio,
+				history: Cell::new(Vec::new()),
 				track_map: Cell::new(track_map),
-				length: tracks.len(),
 				tracks,
 				repeats: Cell::new(time.unwrap_or_default()),
 			})
 		};
 		match song
 			.into_iter()
-			.enumerate()
-			.map(|(index, track)| Ok((index, track.try_into()?)))
-			.collect::<Result<Vec<(usize, Track)>, Error>>()
+			.map(Track::try_from)
+			.collect::<Result<Vec<Track>, Error>>()
 			.map(f)
 		{
 			Err(error) | Ok(Err(error)) => Err(error)?,
@@ -339,34 +759,92 @@ impl TryFrom<SerDePlaylist> for Playlist {
 	}
 }
 
+impl ErrorPolicy {
+	/// Every [`ErrorPolicy`], paired with its name.
+	const NAMES: [(Self, &'static str); 3] = [(Self::Skip, "skip"), (Self::Abort, "abort"), (Self::Prompt, "prompt")];
+
+	#[inline]
+	/// Get the [`ErrorPolicy`] with the given name (e.g.: from `--on-error`).
+	pub fn from_name(name: &str) -> Option<Self> {
+		Self::NAMES
+			.iter()
+			.find(|(_, other)| *other == name)
+			.map(|(policy, _)| *policy)
+	}
+}
+
 impl Track {
 	/// Load the file, and play it back.
 	pub fn play_through(&self, data: &Playhandle) -> Result<ControlFlow, Error> {
-		data.stream_play(File::open(&self.file_path)?)?;
+		let opened = File::open(&self.file_path)
+			.map_err(Error::from)
+			.and_then(|file| data.stream_play(file));
+		let length = match opened {
+			Ok(length) => length,
+			Err(why) => return data.broken_handle(self, why),
+		}
+		.or(self
+			.tags_get()
+			.duration_get());
+		let is_long = length.is_some_and(|length| length >= THRESHOLD);
 
 		let controls = data
 			.io_handle_get()
 			.controls_get();
+		let bookmarks = data.bookmarks_get();
 		let mut whole_elapsed_time = Duration::ZERO;
 		let decrement: fn(usize) -> usize = |old| old - (old > 0) as usize;
 		let increment: fn(usize) -> usize = |old| old + 1;
+		let interrupt = |kind: RecordKind, listened: Duration| -> Result<(), Error> {
+			data.track_record(kind, listened, self);
+			data.event_emit(Event::TrackFinished {
+				track: self
+					.file_path
+					.clone(),
+				listened: listened.as_secs_f64(),
+				skipped: true,
+			});
+			if is_long {
+				bookmarks.position_set(&self.file_path, data.playback_position_get())?
+			}
+			Ok(())
+		};
 
 		data.playback_play();
+		if let Some(position) = bookmarks.position_get(&self.file_path) {
+			data.playback_seek(position)?
+		}
+		log!(Info; "playing '{}'", self.file_path.display());
+		data.track_record(RecordKind::Started, Duration::ZERO, self);
+		data.event_emit(Event::TrackStarted {
+			track: self
+				.file_path
+				.clone(),
+		});
 		while !data.playback_has_ended() {
 			let moment = Instant::now();
+			data.status_publish(self, length);
 
-			match controls.signal_receive() {
+			let received = controls.signal_receive();
+			if let Ok(signal) = received {
+				log!(Debug; "handling the signal '{}'", signal.name())
+			}
+			match received {
 				Err(TryRecvError::Empty) => {},
 
 				Ok(Signal::Exit) => {
+					interrupt(RecordKind::Exited, whole_elapsed_time)?;
 					data.playback_clear();
 					clear()?;
 					return Ok(ControlFlow::Break);
 				},
 
 				Ok(signal) if signal.is_skip() => {
+					interrupt(RecordKind::Skipped, whole_elapsed_time)?;
 					data.playback_clear();
 					clear()?;
+					data.skipped_back
+						.set(signal.is_track_skip() && !signal.is_next_skip());
 					let setter = if signal.is_next_skip() {
 						increment
 					} else {
@@ -383,28 +861,73 @@ impl Track {
 					})(data, setter)?;
 					return Ok(ControlFlow::Skip);
 				},
-				Ok(Signal::Play) => data.playback_toggle(),
+				Ok(Signal::Play) => {
+					data.playback_toggle();
+					data.event_emit(Event::Paused {
+						paused: data.playback_is_paused(),
+					})
+				},
 
 				Ok(Signal::PlaylistReset) => {
+					interrupt(RecordKind::Skipped, whole_elapsed_time)?;
 					data.playlist_index_reset();
 					return Ok(().into());
 				},
 				Ok(Signal::TrackReset) => {
+					interrupt(RecordKind::Skipped, whole_elapsed_time)?;
 					data.track_index_reset();
 					return Ok(().into());
 				},
+				Ok(Signal::TrackRestart) => {
+					bookmarks.position_unset(&self.file_path)?;
+					data.playback_seek(Duration::ZERO)?
+				},
 
-				Ok(signal) if signal.is_volume() => {
-					match signal {
-						Signal::VolumeIncrease => data.volume_increment(),
-						Signal::VolumeDecrease => data.volume_decrement(),
-						Signal::Mute => data.volume_mute(),
-						Signal::VolumeReset => data.volume_reset(),
-						_ => unreachable!(),
+				Ok(Signal::QueueNext) => data.selection_queue_next(self),
+				Ok(Signal::QueueEnd) => data.selection_queue_end(self),
+
+				Ok(Signal::SelectionNext) => data.selection_next(),
+				Ok(Signal::SelectionBack) => data.selection_back(),
+				Ok(signal @ (Signal::SelectionPlay | Signal::TrackJump | Signal::PlaylistJump)) => {
+					let number = controls.target_get();
+					let target = match signal {
+						Signal::SelectionPlay => data.selection_play(),
+						Signal::TrackJump => data
+							.playlist_index_get()
+							.ok()
+							.filter(|playlist_index| {
+								number
+									.checked_sub(1)
+									.is_some_and(|position| data.jump_to(*playlist_index, position).is_ok())
+							}),
+						_ => number
+							.checked_sub(1)
+							.filter(|playlist_index| *playlist_index < data.playlists_count()),
+					};
+					if let Some(playlist_index) = target {
+						interrupt(RecordKind::Skipped, whole_elapsed_time)?;
+						data.playback_clear();
+						clear()?;
+						// NOTE: jumping to a playlist starts it over, even if it is the current one.
+						if data.playlist_index_get() == Ok(playlist_index) && !matches!(signal, Signal::PlaylistJump) {
+							return Ok(ControlFlow::Skip);
+						}
+						data.playlist_index_try_set(|_| playlist_index)?;
+						return Ok(ControlFlow::SkipSkip);
 					}
-					data.volume_update()
 				},
 
+				Ok(Signal::SearchUpdate) => data.search(
+					controls
+						.entry_get()
+						.strip_prefix('/')
+						.unwrap_or_default(),
+				),
+				Ok(Signal::SearchNext) => data.search_next(),
+				Ok(Signal::SearchBack) => data.search_back(),
+
+				Ok(signal) if signal.is_volume() => data.volume_apply(signal),
+
 				Ok(_) => unreachable!(),
 
 				Err(TryRecvError::Disconnected) => Err(ChannelError::Disconnect)?,
@@ -414,6 +937,15 @@ impl Track {
 				whole_elapsed_time += moment.elapsed()
 			}
 		}
+		bookmarks.position_unset(&self.file_path)?;
+		data.track_record(RecordKind::Finished, whole_elapsed_time, self);
+		data.event_emit(Event::TrackFinished {
+			track: self
+				.file_path
+				.clone(),
+			listened: whole_elapsed_time.as_secs_f64(),
+			skipped: false,
+		});
 		if self.repeats_can() {
 			self.repeats_update();
 			return self.play_through(data);
@@ -422,6 +954,61 @@ impl Track {
 		Ok(().into())
 	}
 
+	#[inline]
+	/// Create a [`Track`], that plays once, from a [`Path`] represented as a [`str`].
+	///
+	/// The string is, before being loaded, passed into the [`fmt_path`] function.
+	pub fn try_from_path(path: impl AsRef<str>) -> Result<Self, Error> {
+		Ok(Self {
+			file_path: fmt_path(path)?,
+			repeats: Cell::new(0),
+			weight: 1,
+			tags: OnceCell::new(),
+			overrides: Tags::default(),
+		})
+	}
+
+	#[inline(always)]
+	/// Get the path to the file that is played.
+	pub fn file_path_get(&self) -> &Path {
+		&self.file_path
+	}
+
+	#[inline]
+	/// Get the [`Tags`] of the file, reading them if that has not happened yet.
+	///
+	/// The overrides of the playlist file replace the read values.
+	pub fn tags_get(&self) -> &Tags {
+		self.tags
+			.get_or_init(|| Tags::read(&self.file_path).overlay(&self.overrides))
+	}
+
+	/// Get the name that the [`Track`] is shown by.
+	///
+	/// This is the name given by its [`Tags`] (or the overrides of the playlist file, if they have not been read yet), and its file name otherwise.\
+	/// Never reads the [`Tags`] itself, so that long lists can be shown without reading every file.
+	pub fn name_get(&self) -> String {
+		self.tags
+			.get()
+			.unwrap_or(&self.overrides)
+			.name_get()
+			.unwrap_or_else(|| {
+				self.file_path
+					.file_name()
+					.unwrap_or_default()
+					.to_string_lossy()
+					.into_owned()
+			})
+	}
+
+	#[inline]
+	/// Get the album that the [`Track`] belongs to, which is the directory its file lies in.
+	pub fn album_get(&self) -> &Path {
+		self.file_path
+			.parent()
+			.unwrap_or(&self.file_path)
+	}
+
 	#[inline(always)]
 	/// Whether or not a [`Track`] can repeat.
 	pub fn repeats_can(&self) -> bool {
@@ -443,12 +1030,25 @@ impl Track {
 impl TryFrom<SerDeTrack> for Track {
 	type Error = Error;
 
-	fn try_from(SerDeTrack { file, time }: SerDeTrack) -> Result<Self, Error> {
-		let file_path = fmt_path(file)?;
+	fn try_from(
+		SerDeTrack {
+			file,
+			time,
+			weight,
+			title,
+			artist,
+			album,
+		}: SerDeTrack,
+	) -> Result<Self, Error> {
+		// NOTE: a file that cannot be found is kept as it is written, and dealt with once it is played (see `ErrorPolicy`).
+		let file_path = fmt_path(&file).unwrap_or_else(|_| PathBuf::from(&*file));
 
 		Ok(Self {
 			file_path,
 			repeats: Cell::new(time.unwrap_or_default()),
+			weight: weight.unwrap_or(1),
+			tags: OnceCell::new(),
+			overrides: Tags::with_names(title, artist, album),
 		})
 	}
 }
@@ -466,6 +1066,14 @@ impl Playhandle {
 			.len()
 	}
 
+	#[inline(always)]
+	/// Get all held [`Playlists`].
+	///
+	/// [`Playlists`]: Playlist
+	pub fn playlists_get(&self) -> &[Playlist] {
+		&self.playlists
+	}
+
 	#[inline(always)]
 	/// Count the number of held [`Tracks`] inside of the current [`Playlists`].
 	///
@@ -531,17 +1139,424 @@ impl Playhandle {
 			.take()
 	}
 
+	#[inline]
+	/// Get the [`Track`] that the pointers point to.
+	pub fn track_current_get(&self) -> Option<&Track> {
+		self.playlists
+			.get(self.playlist_index_get().ok()?)?
+			.nth(unsafe { self.track_index_get_unchecked() })
+	}
+
+	#[inline]
+	/// Count the number of queued [`Tracks`].
+	///
+	/// [`Tracks`]: Track
+	pub fn queue_count(&self) -> usize {
+		let queue = self
+			.queue
+			.take();
+		let count = queue.len();
+		self.queue
+			.set(queue);
+		count
+	}
+
+	#[inline]
+	/// Queue a [`Track`] to be played next, before the already queued ones.
+	pub fn queue_push_front(&self, track: Track) {
+		let mut queue = self
+			.queue
+			.take();
+		queue.push_front(track);
+		self.queue
+			.set(queue)
+	}
+
+	#[inline]
+	/// Queue a [`Track`] to be played after the already queued ones.
+	pub fn queue_push_back(&self, track: Track) {
+		let mut queue = self
+			.queue
+			.take();
+		queue.push_back(track);
+		self.queue
+			.set(queue)
+	}
+
+	#[inline]
+	/// Take the [`Track`] that is queued to be played next.
+	pub fn queue_pop(&self) -> Option<Track> {
+		let mut queue = self
+			.queue
+			.take();
+		let track = queue.pop_front();
+		self.queue
+			.set(queue);
+		track
+	}
+
+	#[inline]
+	/// Remove the nth queued [`Track`].
+	pub fn queue_remove(&self, index: usize) -> Option<Track> {
+		let mut queue = self
+			.queue
+			.take();
+		let track = queue.remove(index);
+		self.queue
+			.set(queue);
+		track
+	}
+
+	#[inline(always)]
+	/// Remove all queued [`Tracks`].
+	///
+	/// [`Tracks`]: Track
+	pub fn queue_clear(&self) {
+		self.queue
+			.take();
+	}
+
+	/// Play the queued [`Tracks`], one after another, until the queue is empty.
+	///
+	/// The pointers are kept where they are, unless the [`Playlist`] is skipped, in which case the skip is returned.
+	///
+	/// [`Tracks`]: Track
+	pub fn queue_play(&self) -> Result<Option<ControlFlow>, Error> {
+		while let Some(track) = self.queue_pop() {
+			let playlist_index = self.playlist_index_get()?;
+			let track_index = unsafe { self.track_index_get_unchecked() };
+			match track.play_through(self) {
+				Ok(ControlFlow::Break) => return Ok(Some(ControlFlow::Break)),
+				Ok(ControlFlow::SkipSkip) => return Ok(Some(ControlFlow::Skip)),
+				Ok(_) | Err(Error::Vector(VectorError::OutOfBounds)) => {},
+				Err(other) => Err(other)?,
+			}
+			if self.playlists_have_ended() {
+				self.has_reached_entire_end
+					.set(true);
+				return Ok(Some(ControlFlow::Skip));
+			}
+			if self.playlist_index_get() != Ok(playlist_index) {
+				return Ok(Some(ControlFlow::Skip));
+			}
+			// NOTE: a queued track running over the end of the playlist does not end it, nor does it move the track-pointer.
+			let _ = self.playlist_has_ended();
+			unsafe { self.track_index_set_unchecked(|_| track_index) }
+		}
+		Ok(None)
+	}
+
+	#[inline(always)]
+	/// Get the selected [`Track`], as a pair of playlist-pointer and index into the [`Playlist`]'s unmapped [`Tracks`].
+	///
+	/// [`Tracks`]: Track
+	pub fn selection_get(&self) -> Option<(usize, usize)> {
+		self.selection
+			.get()
+	}
+
+	#[inline]
+	/// Select the [`Track`] that queueing instructions act on.
+	pub fn selection_set(&self, playlist_index: usize, track_index: usize) -> Result<(), VectorError> {
+		self.playlists
+			.get(playlist_index)
+			.ok_or(VectorError::OutOfBounds)?
+			.tracks
+			.get(track_index)
+			.ok_or(VectorError::OutOfBounds)?;
+		self.selection
+			.set(Some((playlist_index, track_index)));
+		Ok(())
+	}
+
+	#[inline(always)]
+	/// Remove the selection, so that queueing instructions act on the current [`Track`] again.
+	pub fn selection_unset(&self) {
+		self.selection
+			.set(None)
+	}
+
+	#[inline]
+	/// Get the selected [`Track`], or the playing one (which may be a queued one) if nothing is selected.
+	pub fn selection_track_get<'a>(&'a self, playing: &'a Track) -> Option<&'a Track> {
+		match self.selection_get() {
+			Some((playlist_index, track_index)) => self
+				.playlists
+				.get(playlist_index)?
+				.tracks
+				.get(track_index),
+			None => Some(playing),
+		}
+	}
+
+	#[inline]
+	/// Queue the selected [`Track`] to be played next.
+	///
+	/// See [`selection_track_get`].
+	///
+	/// [`selection_track_get`]: Self::selection_track_get
+	pub fn selection_queue_next(&self, playing: &Track) {
+		if let Some(track) = self.selection_track_get(playing) {
+			self.queue_push_front(track.clone())
+		}
+	}
+
+	#[inline]
+	/// Queue the selected [`Track`] to be played after the already queued ones.
+	///
+	/// See [`selection_track_get`].
+	///
+	/// [`selection_track_get`]: Self::selection_track_get
+	pub fn selection_queue_end(&self, playing: &Track) {
+		if let Some(track) = self.selection_track_get(playing) {
+			self.queue_push_back(track.clone())
+		}
+	}
+
+	#[inline(always)]
+	/// Move the selection to the following [`Track`], in shuffled order.
+	///
+	/// See [`selection_shift`].
+	///
+	/// [`selection_shift`]: Self::selection_shift
+	pub fn selection_next(&self) {
+		self.selection_shift(true)
+	}
+
+	#[inline(always)]
+	/// Move the selection to the preceding [`Track`], in shuffled order.
+	///
+	/// See [`selection_shift`].
+	///
+	/// [`selection_shift`]: Self::selection_shift
+	pub fn selection_back(&self) {
+		self.selection_shift(false)
+	}
+
+	/// Move the selection by one [`Track`], in shuffled order.
+	///
+	/// Nothing being selected counts as the current [`Track`] being selected.\
+	/// The selection moves over into the neighbouring [`Playlists`], and stops at the very first and last [`Track`].
+	///
+	/// [`Playlists`]: Playlist
+	fn selection_shift(&self, forwards: bool) {
+		let (mut playlist_index, mut position) = match self.selection_get() {
+			Some((playlist_index, track_index)) => (
+				playlist_index,
+				self.playlists[playlist_index]
+					.position_get(track_index)
+					.unwrap_or_default(),
+			),
+			None => match self.playlist_index_get() {
+				Ok(playlist_index) => (playlist_index, unsafe { self.track_index_get_unchecked() }),
+				Err(_) => return,
+			},
+		};
+		if forwards {
+			if position + 1 < self.playlists[playlist_index].tracks_count() {
+				position += 1
+			} else if playlist_index + 1 < self.playlists_count() {
+				(playlist_index, position) = (playlist_index + 1, 0)
+			}
+		} else if position > 0 {
+			position -= 1
+		} else if playlist_index > 0 {
+			playlist_index -= 1;
+			position = self.playlists[playlist_index]
+				.tracks_count()
+				.saturating_sub(1)
+		}
+		if let Some(track_index) = self.playlists[playlist_index].index_get(position) {
+			self.selection
+				.set(Some((playlist_index, track_index)))
+		}
+	}
+
+	/// Continue with the selected [`Track`], once the current one is stopped, and remove the selection.
+	///
+	/// Returns the playlist-pointer of the selected [`Track`], or [`None`] if nothing is selected.
+	pub fn selection_play(&self) -> Option<usize> {
+		let selection = self
+			.selection
+			.take()?;
+		self.jump
+			.set(Some(selection));
+		Some(selection.0)
+	}
+
+	/// Search the file names of every loaded [`Track`], and select the best match.
+	///
+	/// The query is matched fuzzily, see [`fuzzy_score`]; equally good matches are kept in playing order.\
+	/// An empty query, or one without matches, removes the selection.
+	pub fn search(&self, query: &str) {
+		let mut matches = Vec::new();
+		if !query
+			.trim()
+			.is_empty()
+		{
+			for (playlist_index, playlist) in self
+				.playlists
+				.iter()
+				.enumerate()
+			{
+				for position in 0..playlist.tracks_count() {
+					let Some(track_index) = playlist.index_get(position) else { continue };
+					let track = &playlist.tracks[track_index];
+					let file_name = track
+						.file_path_get()
+						.file_name()
+						.unwrap_or_default()
+						.to_string_lossy();
+					// NOTE: the name given by the tags is only searched once they have been read.
+					if let Some(score) = fuzzy_score(query, &track.name_get()).max(fuzzy_score(query, &file_name)) {
+						matches.push((score, (playlist_index, track_index)))
+					}
+				}
+			}
+		}
+		matches.sort_by(|(score, _), (other, _)| other.cmp(score));
+		let matches: Vec<(usize, usize)> = matches
+			.into_iter()
+			.map(|(_, found)| found)
+			.collect();
+		self.selection
+			.set(matches.first().copied());
+		self.search
+			.set((matches, 0));
+	}
+
+	#[inline(always)]
+	/// Select the following match of the last search, wrapping around.
+	///
+	/// See [`search_shift`].
+	///
+	/// [`search_shift`]: Self::search_shift
+	pub fn search_next(&self) {
+		self.search_shift(true)
+	}
+
+	#[inline(always)]
+	/// Select the preceding match of the last search, wrapping around.
+	///
+	/// See [`search_shift`].
+	///
+	/// [`search_shift`]: Self::search_shift
+	pub fn search_back(&self) {
+		self.search_shift(false)
+	}
+
+	/// Select a neighbouring match of the last search.
+	fn search_shift(&self, forwards: bool) {
+		let (matches, mut index) = self
+			.search
+			.take();
+		if !matches.is_empty() {
+			index = if forwards {
+				(index + 1) % matches.len()
+			} else {
+				(index + matches.len() - 1) % matches.len()
+			};
+			self.selection
+				.set(Some(matches[index]));
+		}
+		self.search
+			.set((matches, index));
+	}
+
+	/// Get the 1-based number of the selected match of the last search, and the amount of matches.
+	pub fn search_count_get(&self) -> (usize, usize) {
+		let (matches, index) = self
+			.search
+			.take();
+		let count = (index + !matches.is_empty() as usize, matches.len());
+		self.search
+			.set((matches, index));
+		count
+	}
+
+	/// Get the numeric entry or search that is being typed, as it is shown to the user.
+	///
+	/// A search is followed by the amount of matches, and the name of the selected one.\
+	/// If nothing is being typed, the notice (if any) is returned instead.
+	pub fn entry_line_get(&self) -> String {
+		let entry = self
+			.io_handle
+			.controls_get()
+			.entry_get();
+		if entry.is_empty() {
+			let notice = self
+				.notice
+				.take();
+			self.notice
+				.set(notice.clone());
+			return notice;
+		}
+		if !entry.starts_with('/') {
+			return entry;
+		}
+		let (number, count) = self.search_count_get();
+		let name = self
+			.selection_get()
+			.and_then(|(playlist_index, track_index)| self.playlists[playlist_index].tracks.get(track_index))
+			.map(Track::name_get)
+			.unwrap_or_default();
+		format!("{entry}  [{number}/{count}] {name}")
+	}
+
+	/// Continue with the [`Track`] at the given position (in shuffled order) of a [`Playlist`], once the current one is stopped.
+	///
+	/// Unlike setting the pointers directly, the jump survives the [`Playlist`] being started (and thus shuffled) anew.\
+	/// Returns an [out of bounds] error, if there is no such [`Track`].
+	///
+	/// [out of bounds]: VectorError::OutOfBounds
+	pub fn jump_to(&self, playlist_index: usize, track_index: usize) -> Result<(), VectorError> {
+		let unmapped = self
+			.playlists
+			.get(playlist_index)
+			.and_then(|playlist| playlist.index_get(track_index))
+			.ok_or(VectorError::OutOfBounds)?;
+		self.jump
+			.set(Some((playlist_index, unmapped)));
+		Ok(())
+	}
+
+	/// Take the position of the [`Track`] to continue with, if it lies in the given [`Playlist`].
+	fn jump_take(&self, playlist_index: usize) -> Option<usize> {
+		match self.jump.get() {
+			Some((target, track_index)) if target == playlist_index => {
+				self.jump
+					.set(None);
+				self.playlists
+					.get(playlist_index)?
+					.position_get(track_index)
+			},
+			_ => None,
+		}
+	}
+
 	/// Play all [`Playlists`] back.
 	///
 	/// See [`ControlFlow`] for more information on the returned data's meanings.
 	///
 	/// [`Playlists`]: Playlist
 	pub fn all_playlists_play(&mut self, should_shuffle: bool) -> Result<ControlFlow, Error> {
+		let flow = self.playlists_play_through(should_shuffle);
+		self.event_emit(Event::Exit);
+		flow
+	}
+
+	/// The loop of [`all_playlists_play`].
+	///
+	/// [`all_playlists_play`]: Self::all_playlists_play
+	fn playlists_play_through(&mut self, should_shuffle: bool) -> Result<ControlFlow, Error> {
 		while self
 			.playlist_index_check()
 			.is_none()
 		{
 			let index = unsafe { self.playlist_index_get_unchecked() };
+			self.event_emit(Event::PlaylistChanged {
+				playlist: index,
+			});
 			let playlist = unsafe {
 				self.playlists
 					.get_unchecked(index)
@@ -565,10 +1580,12 @@ impl Playhandle {
 
 	#[inline(always)]
 	/// Play a single source back.
+	///
+	/// Returns the total duration of the source, if it is known.
 	pub fn stream_play(
 		&self,
 		source: impl Read + Seek + Send + Sync + 'static,
-	) -> Result<(), Error> {
+	) -> Result<Option<Duration>, Error> {
 		self.io_handle
 			.stream_play(source)
 	}
@@ -664,6 +1681,7 @@ impl Playhandle {
 				.set(true);
 			Err(VectorError::OutOfBounds)?
 		}
+		log!(Info; "playlist-pointer set to {new_index}");
 		self.current_playlist_index
 			.set(new_index);
 		Ok(())
@@ -723,6 +1741,7 @@ impl Playhandle {
 	/// This function cannot guarantee that the playlist-pointer will not be out of bounds.
 	pub unsafe fn playlist_index_set_unchecked(&self, setter: impl FnOnce(usize) -> usize) {
 		let new = setter(self.playlist_index_get_unchecked());
+		log!(Info; "playlist-pointer set to {new}");
 		self.current_playlist_index
 			.set(new)
 	}
@@ -735,6 +1754,7 @@ impl Playhandle {
 	/// This function cannot guarantee that the track-pointer will not be out of bounds.
 	pub unsafe fn track_index_set_unchecked(&self, setter: impl FnOnce(usize) -> usize) {
 		let new = setter(self.track_index_get_unchecked());
+		log!(Debug; "track-pointer set to {new}");
 		self.playback_clear();
 		self.current_track_index
 			.set(new)
@@ -751,6 +1771,12 @@ impl Playhandle {
 		self.io_handle
 	}
 
+	#[inline(always)]
+	/// Get a reference to the underlying [`Bookmarks`].
+	pub fn bookmarks_get(&self) -> &Bookmarks {
+		&self.bookmarks
+	}
+
 	#[inline]
 	/// Get a clamped version of the internal volume.
 	///
@@ -779,9 +1805,30 @@ impl Playhandle {
 	}
 
 	#[inline(always)]
-	/// Set the volume back to the default (1.0).
+	/// Set the volume back to the default (1.0, unless changed with [`volume_default_set`]).
+	///
+	/// [`volume_default_set`]: Self::volume_default_set
 	pub fn volume_reset(&self) {
-		self.volume_set_raw(|_| 1.0)
+		self.volume_set_raw(|_| self.volume_default)
+	}
+
+	/// Set the volume that the volume is reset to, and reset the volume to it.
+	pub fn volume_default_set(&mut self, volume: f32) {
+		self.volume_default = volume;
+		self.volume_reset();
+		self.volume_update()
+	}
+
+	#[inline(always)]
+	/// Get the amount by which the volume changes per volume [`Signal`].
+	pub fn step_get(&self) -> f32 {
+		self.step
+	}
+
+	#[inline(always)]
+	/// Set the amount by which the volume changes per volume [`Signal`].
+	pub fn step_set(&mut self, step: f32) {
+		self.step = step
 	}
 
 	#[inline(always)]
@@ -811,7 +1858,7 @@ impl Playhandle {
 	/// [`volume_decrement`]: Self::volume_decrement
 	/// [`volume_update`]: Self::volume_update
 	pub fn volume_increment(&self) {
-		self.volume_set_raw(|old| old + STEP)
+		self.volume_set_raw(|old| old + self.step)
 	}
 
 	#[inline(always)]
@@ -824,7 +1871,22 @@ impl Playhandle {
 	/// [`volume_increment`]: Self::volume_increment
 	/// [`volume_update`]: Self::volume_update
 	pub fn volume_decrement(&self) {
-		self.volume_set_raw(|old| old - STEP)
+		self.volume_set_raw(|old| old - self.step)
+	}
+
+	/// Change the volume according to a volume [`Signal`], and let it take effect.
+	pub fn volume_apply(&self, signal: Signal) {
+		match signal {
+			Signal::VolumeIncrease => self.volume_increment(),
+			Signal::VolumeDecrease => self.volume_decrement(),
+			Signal::Mute => self.volume_mute(),
+			Signal::VolumeReset => self.volume_reset(),
+			_ => unreachable!(),
+		}
+		self.volume_update();
+		self.event_emit(Event::VolumeChanged {
+			volume: self.volume_get(),
+		})
 	}
 
 	/// Update the volume on the internal [`Sink`].
@@ -901,6 +1963,23 @@ impl Playhandle {
 			.clear()
 	}
 
+	#[inline]
+	/// Get the position of the current source.
+	pub fn playback_position_get(&self) -> Duration {
+		self.io_handle_get()
+			.playback_get()
+			.get_pos()
+	}
+
+	#[inline]
+	/// Move the current source to a position.
+	pub fn playback_seek(&self, position: Duration) -> Result<(), Error> {
+		self.io_handle_get()
+			.playback_get()
+			.try_seek(position)
+			.map_err(Error::from)
+	}
+
 	#[inline(always)]
 	/// Find out if the internal [`Sink`] is paused or not.
 	///
@@ -910,8 +1989,244 @@ impl Playhandle {
 			.get()
 	}
 
+	#[inline(always)]
+	/// Stream an [`Event`] to the subscribers of the control socket.
+	pub fn event_emit(&self, event: Event) {
+		self.io_handle
+			.event_emit(event)
+	}
+
+	/// Keep what happened to a [`Track`] in the [`Summary`], and record it in the [`History`], if there is one.
+	pub fn track_record(&self, kind: RecordKind, listened: Duration, track: &Track) {
+		let path = track.file_path_get();
+		let mut summary = self
+			.summary
+			.take();
+		match kind {
+			// NOTE: the track that plays whilst quitting was neither played, nor skipped.
+			RecordKind::Started | RecordKind::Exited => {},
+			RecordKind::Finished => summary.played += 1,
+			RecordKind::Skipped => summary.skipped += 1,
+		}
+		if matches!(kind, RecordKind::Started) {
+			self.broken_streak
+				.set(0)
+		} else {
+			self.notice
+				.take();
+		}
+		summary.listened += listened;
+		summary.last = Some((PathBuf::from(path), self.playback_position_get()));
+		self.summary
+			.set(summary);
+		if let Some(history) = &self.history {
+			// NOTE: a history that cannot be written to must not stop the playback.
+			let _ = history.record(kind, listened, path);
+		}
+	}
+
+	/// Deal with a [`Track`] that cannot be played, according to the [`ErrorPolicy`].
+	///
+	/// Unless aborting, the [`Track`] is skipped as if it had been played (ignoring its repeats).\
+	/// If nothing of the current [`Playlist`] could be played in a while, the [`Playlist`] is skipped instead.
+	pub fn broken_handle(&self, track: &Track, why: Error) -> Result<ControlFlow, Error> {
+		let path = track.file_path_get();
+		log!(Error; "playing '{}'; {why}", path.display());
+		let mut summary = self
+			.summary
+			.take();
+		if !summary
+			.broken
+			.iter()
+			.any(|(other, _)| other == path)
+		{
+			summary
+				.broken
+				.push((PathBuf::from(path), why.to_string()))
+		}
+		self.summary
+			.set(summary);
+		let notice = format!("Cannot play '{}'; {why}", track.name_get());
+		match self.error_policy {
+			ErrorPolicy::Abort => Err(why)?,
+			ErrorPolicy::Skip => self
+				.notice
+				.set(notice),
+			ErrorPolicy::Prompt => {
+				self.notice
+					.set(format!("{notice}  (exit, or skip to the next track or playlist)"));
+				let controls = self
+					.io_handle
+					.controls_get();
+				let signal = loop {
+					self.status_publish(track, None);
+					match controls.signal_await(STATUS_INTERVAL) {
+						Ok(signal @ (Signal::Exit | Signal::TrackNext | Signal::PlaylistNext)) => break signal,
+						Ok(signal) if signal.is_volume() => self.volume_apply(signal),
+						Ok(signal) => log!(Debug; "ignoring the signal '{}', whilst prompting", signal.name()),
+						Err(RecvTimeoutError::Timeout) => {},
+						Err(RecvTimeoutError::Disconnected) => Err(ChannelError::Disconnect)?,
+					}
+				};
+				self.notice
+					.set(notice);
+				match signal {
+					Signal::Exit => return Ok(ControlFlow::Break),
+					Signal::PlaylistNext => {
+						self.broken_streak
+							.set(0);
+						let _ = self.playlist_index_try_set(|old| old + 1);
+						return Ok(ControlFlow::SkipSkip);
+					},
+					_ => {},
+				}
+			},
+		}
+		let streak = self.broken_streak.get() + 1;
+		let count = self
+			.playlist_index_get()
+			.ok()
+			.and_then(|index| self.playlists.get(index))
+			.map_or(0, Playlist::tracks_count);
+		if streak >= count {
+			log!(Warn; "skipping playlist {}, as none of its tracks can be played", self.current_playlist_index.get());
+			self.broken_streak
+				.set(0);
+			let _ = self.playlist_index_try_set(|old| old + 1);
+			return Ok(ControlFlow::SkipSkip);
+		}
+		self.broken_streak
+			.set(streak);
+		self.track_index_try_set(|old| old + 1)?;
+		Ok(().into())
+	}
+
+	#[inline(always)]
+	/// Set what is done with [`Tracks`] that cannot be played.
+	///
+	/// [`Tracks`]: Track
+	pub fn error_policy_set(&mut self, policy: ErrorPolicy) {
+		self.error_policy = policy
+	}
+
+	#[inline]
+	/// Get what happened during this session, so far.
+	pub fn summary_get(&self) -> Summary {
+		let summary = self
+			.summary
+			.take();
+		self.summary
+			.set(summary.clone());
+		summary
+	}
+
+	#[inline(always)]
+	/// Record started, finished, skipped and exited [`Tracks`] in a [`History`].
+	///
+	/// [`Tracks`]: Track
+	pub fn history_set(&mut self, history: History) {
+		self.history = Some(history)
+	}
+
+	#[inline(always)]
+	/// Set the format of the status line.
+	///
+	/// The following placeholders are replaced:
+	/// - `{track}`: the name of the playing [`Track`] (see [`Track::name_get`]), `{path}`: its whole path.
+	/// - `{title}`, `{artist}`, `{album}`: read from the file's [`Tags`], empty if missing.
+	/// - `{position}`: the position inside of the playing file (e.g.: `3:07`).
+	/// - `{volume}`: the volume, in percent.
+	/// - `{state}`: either `playing` or `paused`.
+	/// - `{playlist}`, `{index}`: the positions of the [`Playlist`] and [`Track`], counting from 1.
+	pub fn status_format_set(&mut self, format: &str) {
+		self.status_format = Some(Box::from(format))
+	}
+
+	/// Fill the placeholders of the status line format in.
+	fn status_line(&self, format: &str, track: &Track) -> String {
+		let path = track.file_path_get();
+		let tags = track.tags_get();
+		format
+			.replace("{track}", &track.name_get())
+			.replace("{title}", tags.title_get().unwrap_or_default())
+			.replace("{artist}", tags.artist_get().unwrap_or_default())
+			.replace("{album}", tags.album_get().unwrap_or_default())
+			.replace("{path}", &path.to_string_lossy())
+			.replace("{position}", &fmt_duration(self.playback_position_get()))
+			.replace("{volume}", &format!("{:.0}%", self.volume_get() * 100.0))
+			.replace("{state}", if self.playback_is_paused() { "paused" } else { "playing" })
+			.replace("{playlist}", &(unsafe { self.playlist_index_get_unchecked() } + 1).to_string())
+			.replace("{index}", &(unsafe { self.track_index_get_unchecked() } + 1).to_string())
+	}
+
+	/// Publish the player's state, with the given playing [`Track`] and its length, to the control socket and the status line (or the full-screen interface).
+	///
+	/// Publications are limited to one per [`STATUS_INTERVAL`].
+	#[cfg_attr(not(feature = "tui"), allow(unused_variables))]
+	pub fn status_publish(&self, track: &Track, length: Option<Duration>) {
+		if self
+			.published
+			.get()
+			.is_some_and(|moment| moment.elapsed() < STATUS_INTERVAL)
+		{
+			return;
+		}
+		self.published
+			.set(Some(Instant::now()));
+		#[cfg(unix)]
+		self.io_handle
+			.status_set(Status {
+				track: Some(PathBuf::from(track.file_path_get())),
+				title: track
+					.tags_get()
+					.title_get()
+					.map(String::from),
+				artist: track
+					.tags_get()
+					.artist_get()
+					.map(String::from),
+				album: track
+					.tags_get()
+					.album_get()
+					.map(String::from),
+				position: self
+					.playback_position_get()
+					.as_secs_f64(),
+				volume: self.volume_get(),
+				paused: self.playback_is_paused(),
+				playlist: unsafe { self.playlist_index_get_unchecked() },
+				index: unsafe { self.track_index_get_unchecked() },
+			});
+		#[cfg(feature = "tui")]
+		if let Some(mut tui) = self
+			.tui
+			.take()
+		{
+			let _ = tui.draw(self, track, length);
+			self.tui
+				.set(Some(tui));
+			return;
+		}
+		let entry = self.entry_line_get();
+		if let Some(format) = &self.status_format {
+			let line = self.status_line(format, track);
+			let _ = status_print(&if entry.is_empty() { line } else { format!("{entry}  {line}") });
+		} else if !entry.is_empty() || self.entry_shown.get() {
+			let _ = status_print(&entry);
+			self.entry_shown
+				.set(!entry.is_empty());
+		}
+	}
+
+	#[cfg(feature = "tui")]
+	#[inline(always)]
+	/// Draw a full-screen interface instead of the status line.
+	pub fn tui_set(&mut self, tui: Tui) {
+		self.tui = Cell::new(Some(tui))
+	}
+
 	/// Initialise a new instance from the input.
-	pub fn raw_parts_from(io_handle: IOHandle, streams_vector: Vec<Playlist>) -> Self {
+	pub fn raw_parts_from(io_handle: IOHandle, bookmarks: Bookmarks, streams_vector: Vec<Playlist>) -> Self {
 		Self {
 			current_track_index: Cell::new(0),
 			current_playlist_index: Cell::new(0),
@@ -921,7 +2236,15 @@ impl Playhandle {
 
 			playlists: streams_vector,
 
+			queue: Cell::new(VecDeque::new()),
+			selection: Cell::new(None),
+			jump: Cell::new(None),
+			skipped_back: Cell::new(false),
+			search: Cell::new((Vec::new(), 0)),
+
 			volume: Cell::new(1.0),
+			volume_default: 1.0,
+			step: STEP,
 			paused: Cell::new(
 				io_handle
 					.playback_get()
@@ -929,12 +2252,24 @@ impl Playhandle {
 			),
 
 			io_handle,
+			bookmarks,
+			history: None,
+			published: Cell::new(None),
+			entry_shown: Cell::new(false),
+			summary: Cell::new(Summary::default()),
+			error_policy: ErrorPolicy::default(),
+			broken_streak: Cell::new(0),
+			notice: Cell::new(String::new()),
+			status_format: None,
+			#[cfg(feature = "tui")]
+			tui: Cell::new(None),
 		}
 	}
 
 	/// Swap the internal playlist with a new one.
 	pub fn playlists_swap(&mut self, new: Vec<Playlist>) {
 		self.playback_clear();
+		self.selection_unset();
 		self.playlists = new;
 		self.playlist_index_reset();
 	}
@@ -944,9 +2279,11 @@ impl TryFrom<Vec<Playlist>> for Playhandle {
 	type Error = Error;
 
 	#[inline(always)]
-	/// Try to instantiate a new [`IOHandle`], instead of passing it into the function.
+	/// Try to instantiate a new [`IOHandle`] and load the [`Bookmarks`], instead of passing them into the function.
 	fn try_from(streams_vector: Vec<Playlist>) -> Result<Self, Error> {
-		IOHandle::try_new().map(|io_handle| Self::raw_parts_from(io_handle, streams_vector))
+		let io_handle = IOHandle::try_new()?;
+		let bookmarks = Bookmarks::try_new()?;
+		Ok(Self::raw_parts_from(io_handle, bookmarks, streams_vector))
 	}
 }
 
@@ -961,3 +2298,34 @@ impl From<()> for ControlFlow {
 		Self::Default
 	}
 }
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Shuffle a [`Playlist`] of sixteen [`Tracks`] once, and get its order.
+	///
+	/// [`Tracks`]: Track
+	fn order_get(seed: u64) -> Vec<usize> {
+		let contents: String = (0..16)
+			.map(|index| format!("[[song]]\nfile = \"/nowhere/{index}.flac\"\n"))
+			.collect();
+		let mut list: SerDePlaylist = toml::from_str(&contents).unwrap();
+		list.seed_set(seed);
+		let playlist = Playlist::try_from(list).unwrap();
+		playlist.shuffle();
+		playlist
+			.track_map
+			.take()
+	}
+
+	#[test]
+	fn shuffle_same_seed_same_order() {
+		assert_eq!(order_get(42), order_get(42));
+	}
+
+	#[test]
+	fn shuffle_other_seed_other_order() {
+		assert_ne!(order_get(1), order_get(2));
+	}
+}
@@ -0,0 +1,26 @@
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+//! The seed of the last session.
+//!
+//! Every session stores the seed that it shuffles with in a plain text file inside of the state directory, so that it can be replayed (`--seed last`).
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+use super::{utilities::state_path, Error};
+use std::{
+	fs::{read_to_string, write},
+	io::{self, ErrorKind},
+};
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// The name of the file, in the state directory, that holds the seed.
+const FILE_NAME: &str = "seed";
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// Store the seed of the current session.
+pub fn store(seed: u64) -> Result<(), Error> {
+	write(state_path(FILE_NAME)?, format!("{seed}\n")).map_err(Error::from)
+}
+
+/// Load the seed of the last session.
+pub fn load() -> Result<u64, Error> {
+	read_to_string(state_path(FILE_NAME)?)?
+		.trim()
+		.parse()
+		.map_err(|_| io::Error::from(ErrorKind::InvalidData).into())
+}
@@ -1,8 +1,20 @@
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
-use super::{utilities::fmt_path, Error, VectorError};
-use serde::Deserialize;
-use std::{fs::read_to_string, num::NonZero};
-use toml::from_str;
+use super::{
+	history::History,
+	library::{Library, Query, Smart},
+	playback::{SortKey, Strategy, Track},
+	tags::Tags,
+	utilities::fmt_path,
+	Error, VectorError,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+	cmp::Ordering,
+	fs::read_to_string,
+	io::{self, ErrorKind},
+	num::NonZero,
+};
+use toml::{from_str, to_string};
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 #[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
 #[cfg_attr(
@@ -10,12 +22,32 @@ use toml::from_str;
 	derive(PartialEq, Eq, PartialOrd, Ord),
 	derive(Hash)
 )]
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 /// A playlist with some metadata.
 pub struct SerDePlaylist {
+	#[serde(default)]
 	pub(crate) song: Vec<SerDeTrack>,
+	/// A library query, whose matches are added to the songs when the playlist is loaded.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub(crate) query: Option<Box<str>>,
+	/// Generate the songs when the playlist is loaded, from the files that match the query (or every indexed one).
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub(crate) smart: Option<Smart>,
+	/// The maximum amount of songs that a query, or a smart playlist, adds.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub(crate) limit: Option<usize>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	pub(crate) time: Option<isize>,
-	pub(crate) vary: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub(crate) vary: Option<SerDeVary>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub(crate) seed: Option<u64>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub(crate) radio: Option<usize>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub(crate) sort: Option<SerDeSort>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub(crate) filter: Option<SerDeFilter>,
 }
 
 #[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
@@ -24,12 +56,77 @@ pub struct SerDePlaylist {
 	derive(PartialEq, Eq, PartialOrd, Ord),
 	derive(Hash)
 )]
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
+#[derive(Clone)]
+#[serde(try_from = "String", into = "String")]
+/// The order that the tracks of a playlist are sorted into, as a comma separated list of [`SortKeys`] (e.g.: `sort = "album,track"`).
+///
+/// Later keys decide between tracks that are equal in the earlier ones.
+///
+/// [`SortKeys`]: SortKey
+pub struct SerDeSort(Vec<SortKey>);
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[cfg_attr(
+	any(debug_assertions, feature = "traits"),
+	derive(PartialEq, Eq, PartialOrd, Ord),
+	derive(Hash)
+)]
+#[derive(Deserialize, Serialize)]
+#[derive(Clone)]
+#[serde(deny_unknown_fields)]
+/// The tags that tracks must have to stay in a playlist (e.g.: `filter = { artist = "queen" }`).
+///
+/// Every given tag must contain its value, ignoring case.
+pub struct SerDeFilter {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub(crate) title: Option<Box<str>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub(crate) artist: Option<Box<str>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub(crate) album: Option<Box<str>>,
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[cfg_attr(
+	any(debug_assertions, feature = "traits"),
+	derive(PartialEq, Eq, PartialOrd, Ord),
+	derive(Hash)
+)]
+#[derive(Deserialize, Serialize)]
+#[derive(Clone, Copy)]
+#[serde(untagged)]
+/// The shuffling setting of a playlist.
+///
+/// Either a plain toggle (e.g.: `vary = false`), or the name of a [`Strategy`] (e.g.: `vary = "album"`).
+pub enum SerDeVary {
+	Toggle(bool),
+	Mode(Strategy),
+}
+
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[cfg_attr(
+	any(debug_assertions, feature = "traits"),
+	derive(PartialEq, Eq, PartialOrd, Ord),
+	derive(Hash)
+)]
+#[derive(Deserialize, Serialize)]
 #[derive(Clone)]
 /// A song path with additional metadata.
+///
+/// The `title`, `artist` and `album` override the ones read from the file's tags, or supply them for files without any.
 pub struct SerDeTrack {
 	pub(crate) file: Box<str>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	pub(crate) time: Option<isize>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub(crate) weight: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub(crate) title: Option<Box<str>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub(crate) artist: Option<Box<str>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub(crate) album: Option<Box<str>>,
 }
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 impl SerDePlaylist {
@@ -62,7 +159,25 @@ impl SerDePlaylist {
 	#[inline(always)]
 	/// Primitive for setting shuffling equal to some state.
 	pub fn vary_set(&mut self, state: bool) {
-		self.vary = state.then_some(true);
+		self.vary = state.then_some(SerDeVary::Toggle(true));
+	}
+
+	#[inline(always)]
+	/// Get the shuffling setting, if any.
+	pub fn vary_get(&self) -> Option<SerDeVary> {
+		self.vary
+	}
+
+	#[inline(always)]
+	/// Primitive for setting shuffling equal to some [`SerDeVary`].
+	pub fn vary_raw_set(&mut self, vary: SerDeVary) {
+		self.vary = Some(vary)
+	}
+
+	#[inline(always)]
+	/// Primitive for setting shuffling equal to some [`Strategy`].
+	pub fn vary_mode_set(&mut self, strategy: Strategy) {
+		self.vary = Some(SerDeVary::Mode(strategy));
 	}
 
 	#[inline(always)]
@@ -71,6 +186,69 @@ impl SerDePlaylist {
 		self.vary_set(false)
 	}
 
+	#[inline(always)]
+	/// Whether the songs are generated when loading, whose order is then kept unless `vary` is set.
+	pub fn is_smart(&self) -> bool {
+		self.smart
+			.is_some()
+	}
+
+	#[inline(always)]
+	/// Get the seed that the shuffling is based on, if any.
+	pub fn seed_get(&self) -> Option<u64> {
+		self.seed
+	}
+
+	#[inline(always)]
+	/// Primitive for setting the shuffling seed.
+	pub fn seed_set(&mut self, seed: u64) {
+		self.seed = Some(seed)
+	}
+
+	#[inline(always)]
+	/// Primitive for setting the shuffling seed equal to null.
+	pub fn seed_unset(&mut self) {
+		self.seed = None
+	}
+
+	#[inline(always)]
+	/// Primitive for enabling radio mode, avoiding the given amount of last played tracks.
+	pub fn radio_set(&mut self, avoid: usize) {
+		self.radio = Some(avoid)
+	}
+
+	#[inline(always)]
+	/// Primitive for disabling radio mode.
+	pub fn radio_unset(&mut self) {
+		self.radio = None
+	}
+
+	#[inline(always)]
+	/// Primitive for sorting the tracks by the given [`SortKeys`].
+	///
+	/// [`SortKeys`]: SortKey
+	pub fn sort_set(&mut self, keys: Vec<SortKey>) {
+		self.sort = Some(SerDeSort(keys))
+	}
+
+	#[inline(always)]
+	/// Primitive for keeping the tracks in the order of the file.
+	pub fn sort_unset(&mut self) {
+		self.sort = None
+	}
+
+	#[inline(always)]
+	/// Primitive for narrowing the tracks down with a [`SerDeFilter`].
+	pub fn filter_set(&mut self, filter: SerDeFilter) {
+		self.filter = Some(filter)
+	}
+
+	#[inline(always)]
+	/// Primitive for keeping every track.
+	pub fn filter_unset(&mut self) {
+		self.filter = None
+	}
+
 	#[inline]
 	/// Filter out [`SerDePlaylist`] [`files`] from audio [`files`].
 	///
@@ -84,8 +262,15 @@ impl SerDePlaylist {
 		let mut rest = Vec::with_capacity(8);
 		let mut outliers = SerDePlaylist {
 			song: Vec::with_capacity(8),
+			query: None,
+			smart: None,
+			limit: None,
 			time: None,
 			vary: None,
+			seed: None,
+			radio: None,
+			sort: None,
+			filter: None,
 		};
 		for path in iterator {
 			match read_to_string(fmt_path(&path)?) {
@@ -96,6 +281,10 @@ impl SerDePlaylist {
 					.push(SerDeTrack {
 						file: path.into_boxed_str(),
 						time: None,
+						weight: None,
+						title: None,
+						artist: None,
+						album: None,
 					}),
 			}
 		}
@@ -105,35 +294,116 @@ impl SerDePlaylist {
 			.collect())
 	}
 
+	/// Write `self` back out, as the contents of a playlist file.
+	///
+	/// The songs that a query, or being smart, added are written as plain songs, so the query, smart and limit keys are left out.\
+	/// Everything else (e.g.: the title, artist and album that a song overrides) is kept.
+	pub fn contents_get(&self) -> Result<String, Error> {
+		let resolved = Self {
+			song: self
+				.song
+				.clone(),
+			query: None,
+			smart: None,
+			limit: None,
+			time: self.time,
+			// NOTE: the written list is no longer smart, so its order is kept explicitly.
+			vary: self
+				.vary
+				.or(self.smart.map(|_| SerDeVary::Toggle(false))),
+			seed: self.seed,
+			radio: self.radio,
+			sort: self
+				.sort
+				.clone(),
+			filter: self
+				.filter
+				.clone(),
+		};
+		to_string(&resolved).map_err(|why| io::Error::new(ErrorKind::InvalidData, why).into())
+	}
+
 	#[inline]
 	/// Merge a list of [`SerDePlaylists`] into a single [`SerDePlaylist`].
 	///
+	/// Sorting and filtering only concern the songs of their own playlist, so every playlist is arranged by its own before the merge (see [`songs_arrange`]).
+	///
 	/// [`SerDePlaylists`]: SerDePlaylist
-	pub fn flatten(lists: Vec<Self>) -> Result<Self, Error> {
+	/// [`songs_arrange`]: Self::songs_arrange
+	pub fn flatten(mut lists: Vec<Self>) -> Result<Self, Error> {
 		let repeats = lists
 			.iter()
 			.min_by_key(|Self { time, .. }| time.unwrap_or_default())
 			.ok_or(VectorError::Empty)?
 			.time
 			.unwrap_or_default();
+		// NOTE: the first explicit setting wins, so that a strategy (e.g.: `vary = "album"`) survives the merge.
+		// The order of smart playlists is only kept if every merged playlist is one, as it is otherwise lost anyway.
 		let shuffle = lists
 			.iter()
-			.find_map(|Self { vary, .. }| match vary {
-				Some(false) | None => Some(false),
-				Some(true) => None,
-			})
-			.ok_or(VectorError::Empty)?;
+			.find_map(|Self { vary, .. }| *vary)
+			.or_else(|| {
+				lists
+					.iter()
+					.all(Self::is_smart)
+					.then_some(SerDeVary::Toggle(false))
+			});
+		let seed = lists
+			.iter()
+			.find_map(|Self { seed, .. }| *seed);
+		let radio = lists
+			.iter()
+			.find_map(|Self { radio, .. }| *radio);
+		for list in lists.iter_mut() {
+			list.songs_arrange()
+		}
 		let tracks: Vec<SerDeTrack> = lists
 			.into_iter()
 			.flat_map(|list| list.song)
 			.collect();
 		Ok(Self {
-			vary: Some(shuffle),
+			vary: shuffle,
 			song: tracks,
+			query: None,
+			smart: None,
+			limit: None,
 			time: Some(repeats),
+			seed,
+			radio,
+			sort: None,
+			filter: None,
 		})
 	}
 
+	/// Apply the filter, and then the sort, to the songs themselves, and forget both.
+	///
+	/// Like when playing, this reads the tags of every song, so it is only done when asked for.
+	pub fn songs_arrange(&mut self) {
+		if self.filter.is_none() && self.sort.is_none() {
+			return;
+		}
+		// NOTE: filters and sorts work on the tags of tracks, which know about the overrides of their songs.
+		let mut pairs: Vec<(Track, SerDeTrack)> = self
+			.song
+			.drain(..)
+			.filter_map(|song| {
+				Track::try_from(song.clone())
+					.ok()
+					.map(|track| (track, song))
+			})
+			.collect();
+		if let Some(filter) = self.filter.take() {
+			pairs.retain(|(track, _)| filter.matches(track.tags_get()))
+		}
+		if let Some(sort) = self.sort.take() {
+			pairs.sort_by(|(one, _), (other, _)| sort.compare(one, other))
+		}
+		self.song = pairs
+			.into_iter()
+			.map(|(_, song)| song)
+			.collect();
+	}
+
 	#[inline(always)]
 	/// Find out if a [`SerDePlaylist`] is empty.
 	///
@@ -145,14 +415,46 @@ impl SerDePlaylist {
 			.is_empty()
 	}
 
-	#[inline(always)]
+	#[inline]
 	/// Load a [`Playlist`] from a [`Path`] represented as a [`String`].
 	///
-	/// The string is, before being loaded, passed into the [`fmt_path`] function.
+	/// The string is, before being loaded, passed into the [`fmt_path`] function.\
+	/// The matches of a query, or the files of a [`Smart`] playlist, are looked up in the [`Library`] and added after the listed songs.
 	///
 	/// [`Path`]: std::path::Path
 	fn try_from_contents(contents: String) -> Result<Self, Error> {
-		from_str(&contents).map_err(Error::from)
+		let mut list: Self = from_str(&contents)?;
+		if list.query.is_none() && list.smart.is_none() {
+			return Ok(list);
+		}
+		let query = list
+			.query
+			.as_deref()
+			.map(Query::parse)
+			.transpose()?;
+		let library = Library::try_new()?;
+		let paths = match list.smart {
+			Some(smart) => library.smart_resolve(smart, query.as_ref(), &History::try_new()?)?,
+			None => library
+				.query(query.as_ref().ok_or(VectorError::Empty)?)
+				.map(|entry| entry.path.clone())
+				.collect(),
+		};
+		list.song
+			.extend(
+				paths
+					.into_iter()
+					.take(list.limit.unwrap_or(usize::MAX))
+					.map(|path| SerDeTrack {
+						file: Box::from(path.to_string_lossy()),
+						time: None,
+						weight: None,
+						title: None,
+						artist: None,
+						album: None,
+					}),
+			);
+		Ok(list)
 	}
 }
 
@@ -167,4 +469,192 @@ impl SerDeTrack {
 	pub fn unset_time(&mut self) {
 		self.time = None
 	}
+	#[inline(always)]
+	/// Set the chance of the track being picked early, when using the [weighted] shuffle.
+	///
+	/// [weighted]: Strategy::Weighted
+	pub fn set_weight(&mut self, value: u32) {
+		self.weight = Some(value)
+	}
+	#[inline(always)]
+	/// Set the weight to default.
+	pub fn unset_weight(&mut self) {
+		self.weight = None
+	}
+	#[inline(always)]
+	/// Override the title that is read from the file's tags.
+	pub fn set_title(&mut self, value: &str) {
+		self.title = Some(Box::from(value))
+	}
+	#[inline(always)]
+	/// Use the title that is read from the file's tags again.
+	pub fn unset_title(&mut self) {
+		self.title = None
+	}
+	#[inline(always)]
+	/// Override the artist that is read from the file's tags.
+	pub fn set_artist(&mut self, value: &str) {
+		self.artist = Some(Box::from(value))
+	}
+	#[inline(always)]
+	/// Use the artist that is read from the file's tags again.
+	pub fn unset_artist(&mut self) {
+		self.artist = None
+	}
+	#[inline(always)]
+	/// Override the album that is read from the file's tags.
+	pub fn set_album(&mut self, value: &str) {
+		self.album = Some(Box::from(value))
+	}
+	#[inline(always)]
+	/// Use the album that is read from the file's tags again.
+	pub fn unset_album(&mut self) {
+		self.album = None
+	}
+}
+
+impl SerDeSort {
+	/// Compare two [`Tracks`] by the [`SortKeys`], in order.
+	///
+	/// [`Tracks`]: Track
+	/// [`SortKeys`]: SortKey
+	pub fn compare(&self, one: &Track, other: &Track) -> Ordering {
+		// NOTE: missing tags sort last, and names ignore case.
+		fn name_compare(one: Option<&str>, other: Option<&str>) -> Ordering {
+			let key = |name: Option<&str>| (name.is_none(), name.map(str::to_lowercase));
+			key(one).cmp(&key(other))
+		}
+		let (one_tags, other_tags) = (one.tags_get(), other.tags_get());
+		self.0
+			.iter()
+			.map(|key| match key {
+				SortKey::Title => name_compare(one_tags.title_get(), other_tags.title_get()),
+				SortKey::Artist => name_compare(one_tags.artist_get(), other_tags.artist_get()),
+				SortKey::Album => name_compare(one_tags.album_get(), other_tags.album_get()),
+				SortKey::Track => {
+					let key = |tags: &Tags| (tags.track_number_get().is_none(), tags.track_number_get());
+					key(one_tags).cmp(&key(other_tags))
+				},
+				SortKey::File => one
+					.file_path_get()
+					.cmp(other.file_path_get()),
+			})
+			.find(|ordering| ordering.is_ne())
+			.unwrap_or(Ordering::Equal)
+	}
+}
+
+impl TryFrom<String> for SerDeSort {
+	type Error = String;
+
+	fn try_from(text: String) -> Result<Self, Self::Error> {
+		text.split(',')
+			.map(|name| match name.trim() {
+				"title" => Ok(SortKey::Title),
+				"artist" => Ok(SortKey::Artist),
+				"album" => Ok(SortKey::Album),
+				"track" => Ok(SortKey::Track),
+				"file" => Ok(SortKey::File),
+				other => Err(format!("unknown sort key '{other}', expected one of: title, artist, album, track, file")),
+			})
+			.collect::<Result<Vec<SortKey>, String>>()
+			.map(Self)
+	}
+}
+
+impl From<SerDeSort> for String {
+	fn from(SerDeSort(keys): SerDeSort) -> Self {
+		keys.iter()
+			.map(|key| match key {
+				SortKey::Title => "title",
+				SortKey::Artist => "artist",
+				SortKey::Album => "album",
+				SortKey::Track => "track",
+				SortKey::File => "file",
+			})
+			.collect::<Vec<&str>>()
+			.join(",")
+	}
+}
+
+impl SerDeFilter {
+	/// Check whether the [`Tags`] of a track contain every value of the filter, ignoring case.
+	pub fn matches(&self, tags: &Tags) -> bool {
+		let contains = |wanted: &Option<Box<str>>, found: Option<&str>| match wanted {
+			Some(wanted) => found.is_some_and(|found| {
+				found
+					.to_lowercase()
+					.contains(&wanted.to_lowercase())
+			}),
+			None => true,
+		};
+		contains(&self.title, tags.title_get()) && contains(&self.artist, tags.artist_get()) && contains(&self.album, tags.album_get())
+	}
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn track_overrides_round_trip() {
+		let track = SerDeTrack {
+			file: Box::from("/nowhere/field recording.flac"),
+			time: Some(2),
+			weight: None,
+			title: Some(Box::from("Rain, \"at night\"")),
+			artist: Some(Box::from("Nobody")),
+			album: Some(Box::from("Outside")),
+		};
+		let parsed: SerDeTrack = from_str(&to_string(&track).unwrap()).unwrap();
+		assert_eq!(parsed, track);
+	}
+
+	#[test]
+	fn flatten_arranges_every_list() {
+		let filtered: SerDePlaylist = from_str(
+			"filter = { artist = \"keep\" }\nsort = \"title\"\n\
+			[[song]]\nfile = \"/nowhere/a\"\ntitle = \"b\"\nartist = \"keep\"\n\
+			[[song]]\nfile = \"/nowhere/b\"\ntitle = \"c\"\nartist = \"drop\"\n\
+			[[song]]\nfile = \"/nowhere/c\"\ntitle = \"a\"\nartist = \"keep\"\n",
+		)
+		.unwrap();
+		let plain: SerDePlaylist = from_str("[[song]]\nfile = \"/nowhere/d\"\nartist = \"drop\"\n").unwrap();
+		let merged = SerDePlaylist::flatten(vec![filtered, plain]).unwrap();
+		let files: Vec<&str> = merged
+			.song
+			.iter()
+			.map(|song| &*song.file)
+			.collect();
+		assert_eq!(files, ["/nowhere/c", "/nowhere/a", "/nowhere/d"]);
+		assert!(merged.sort.is_none() && merged.filter.is_none());
+	}
+
+	#[test]
+	fn flatten_smart_order() {
+		let smart = || from_str::<SerDePlaylist>("smart = \"unplayed\"\n[[song]]\nfile = \"/nowhere/a\"\n").unwrap();
+		let plain: SerDePlaylist = from_str("[[song]]\nfile = \"/nowhere/b\"\n").unwrap();
+		assert_eq!(SerDePlaylist::flatten(vec![smart(), plain]).unwrap().vary, None);
+		assert_eq!(SerDePlaylist::flatten(vec![smart(), smart()]).unwrap().vary, Some(SerDeVary::Toggle(false)));
+		let mut shuffled = smart();
+		shuffled.vary_mode_set(Strategy::Plain);
+		assert_eq!(
+			SerDePlaylist::flatten(vec![shuffled, smart()]).unwrap().vary,
+			Some(SerDeVary::Mode(Strategy::Plain))
+		);
+		assert_eq!(
+			from_str::<SerDePlaylist>(&smart().contents_get().unwrap()).unwrap().vary,
+			Some(SerDeVary::Toggle(false))
+		);
+	}
+
+	#[test]
+	fn playlist_contents_round_trip() {
+		let contents = "vary = false\n[[song]]\nfile = \"/nowhere/a.flac\"\ntitle = \"A\"\n\n[[song]]\nfile = \"/nowhere/b.flac\"\nartist = \"B\"\nalbum = \"C\"\n";
+		let list: SerDePlaylist = from_str(contents).unwrap();
+		let written = list
+			.contents_get()
+			.unwrap();
+		assert_eq!(from_str::<SerDePlaylist>(&written).unwrap(), list);
+	}
 }
@@ -0,0 +1,202 @@
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+//! Metadata of audio files, as read from their tags (ID3, Vorbis comments, MP4 atoms, or FLAC blocks).
+//!
+//! Reading is best-effort: a file without (readable) tags simply has empty [`Tags`].\
+//! Every [`Track`] reads its tags once, when they are first asked for, which usually is when it starts playing.
+//!
+//! [`Track`]: crate::playback::Track
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+use std::{fs::File, path::Path, time::Duration};
+use symphonia::{
+	core::{
+		formats::FormatOptions,
+		io::MediaSourceStream,
+		meta::{MetadataOptions, MetadataRevision, StandardTagKey},
+		probe::Hint,
+	},
+	default::get_probe,
+};
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[cfg_attr(any(debug_assertions, feature = "debug"), derive(Debug))]
+#[derive(Clone, Default)]
+/// The metadata of a single file.
+pub struct Tags {
+	title: Option<Box<str>>,
+	artist: Option<Box<str>>,
+	album: Option<Box<str>>,
+	track_number: Option<u32>,
+	/// The year of release.
+	year: Option<u32>,
+	/// The length of the file, as stated by its container.
+	duration: Option<Duration>,
+	/// Whether a picture (usually: the album cover) is embedded.
+	has_cover: bool,
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+impl Tags {
+	/// Read the tags of a file.
+	///
+	/// Files that cannot be opened, or are of an unknown format, have empty tags.
+	pub fn read(path: &Path) -> Self {
+		let mut tags = Self::default();
+		let Ok(file) = File::open(path) else { return tags };
+		let mut hint = Hint::new();
+		if let Some(extension) = path
+			.extension()
+			.and_then(|extension| extension.to_str())
+		{
+			hint.with_extension(extension);
+		}
+		let Ok(mut probed) = get_probe().format(
+			&hint,
+			MediaSourceStream::new(Box::new(file), Default::default()),
+			&FormatOptions::default(),
+			&MetadataOptions::default(),
+		) else {
+			return tags;
+		};
+
+		// NOTE: tags in front of the container (e.g.: ID3 in an MP3) are found by the probe, others by the format reader.
+		if let Some(revision) = probed
+			.metadata
+			.get()
+			.as_ref()
+			.and_then(|metadata| metadata.current())
+		{
+			tags.revision_apply(revision)
+		}
+		if let Some(revision) = probed
+			.format
+			.metadata()
+			.current()
+		{
+			tags.revision_apply(revision)
+		}
+		tags.duration = probed
+			.format
+			.default_track()
+			.and_then(|track| {
+				let parameters = &track.codec_params;
+				let time = parameters
+					.time_base?
+					.calc_time(parameters.n_frames?);
+				Some(Duration::from_secs(time.seconds) + Duration::from_secs_f64(time.frac))
+			});
+		tags
+	}
+
+	/// Create [`Tags`] that hold nothing but the given names, e.g.: to override the ones of a file with.
+	pub fn with_names(title: Option<Box<str>>, artist: Option<Box<str>>, album: Option<Box<str>>) -> Self {
+		Self {
+			title,
+			artist,
+			album,
+			..Self::default()
+		}
+	}
+
+	/// Lay the names of other [`Tags`] over `self`, replacing the ones that they hold.
+	pub fn overlay(mut self, other: &Self) -> Self {
+		for (field, other) in [
+			(&mut self.title, &other.title),
+			(&mut self.artist, &other.artist),
+			(&mut self.album, &other.album),
+		] {
+			if other.is_some() {
+				field.clone_from(other)
+			}
+		}
+		self
+	}
+
+	/// Take over every known tag of a [`MetadataRevision`], keeping values that were already read.
+	fn revision_apply(&mut self, revision: &MetadataRevision) {
+		for tag in revision.tags() {
+			let value = tag
+				.value
+				.to_string();
+			let value = value.trim();
+			if value.is_empty() {
+				continue;
+			}
+			let field = match tag.std_key {
+				Some(StandardTagKey::TrackTitle) => &mut self.title,
+				Some(StandardTagKey::Artist) => &mut self.artist,
+				Some(StandardTagKey::Album) => &mut self.album,
+				Some(StandardTagKey::TrackNumber) => {
+					// NOTE: track numbers are often stored as `number/total`.
+					self.track_number = self
+						.track_number
+						.or_else(|| {
+							value
+								.split('/')
+								.next()?
+								.trim()
+								.parse()
+								.ok()
+						});
+					continue;
+				},
+				Some(StandardTagKey::Date | StandardTagKey::ReleaseDate | StandardTagKey::OriginalDate) => {
+					// NOTE: dates come in many shapes (e.g.: `1975`, `1975-10-31`, `31.10.1975`), the first run of exactly four digits is taken as the year.
+					self.year = self
+						.year
+						.or_else(|| {
+							value
+								.split(|character: char| !character.is_ascii_digit())
+								.find(|part| part.len() == 4)?
+								.parse()
+								.ok()
+						});
+					continue;
+				},
+				_ => continue,
+			};
+			if field.is_none() {
+				*field = Some(Box::from(value))
+			}
+		}
+		self.has_cover |= !revision
+			.visuals()
+			.is_empty();
+	}
+
+	#[inline(always)]
+	/// Get the title.
+	pub fn title_get(&self) -> Option<&str> { self.title.as_deref() }
+
+	#[inline(always)]
+	/// Get the artist.
+	pub fn artist_get(&self) -> Option<&str> { self.artist.as_deref() }
+
+	#[inline(always)]
+	/// Get the album's name.
+	pub fn album_get(&self) -> Option<&str> { self.album.as_deref() }
+
+	#[inline(always)]
+	/// Get the number of the track on its album.
+	pub fn track_number_get(&self) -> Option<u32> { self.track_number }
+
+	#[inline(always)]
+	/// Get the year of release.
+	pub fn year_get(&self) -> Option<u32> { self.year }
+
+	#[inline(always)]
+	/// Get the length of the file, as stated by its container.
+	pub fn duration_get(&self) -> Option<Duration> { self.duration }
+
+	#[inline(always)]
+	/// Whether or not a picture (usually: the album cover) is embedded.
+	pub fn cover_is_present(&self) -> bool { self.has_cover }
+
+	/// Get the name that the file is shown by, which is `artist - title`, or just the title.
+	///
+	/// Returns [`None`] if there is no title.
+	pub fn name_get(&self) -> Option<String> {
+		let title = self.title_get()?;
+		Some(match self.artist_get() {
+			Some(artist) => format!("{artist} - {title}"),
+			None => String::from(title),
+		})
+	}
+}
@@ -0,0 +1,163 @@
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+//! A full-screen interface, as an alternative to the status line.
+//!
+//! The interface lists the loaded [`Playlists`], and the [`Tracks`] of either the current or the selected one, in shuffled order.\
+//! Below, it shows the progress of the playing [`Track`], and the volume.
+//!
+//! It only draws, inputs are still read by the [`Controls`]; the selection is moved with the `selection-next` and `selection-back` [`Signals`], and played with `selection-play`.\
+//! A numeric entry or search that is being typed is shown in the title of the [`Tracks`].
+//!
+//! [`Playlists`]: crate::playback::Playlist
+//! [`Tracks`]: Track
+//! [`Controls`]: crate::in_out::Controls
+//! [`Signals`]: crate::in_out::Signal
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+use super::{
+	playback::{Playhandle, Track},
+	utilities::fmt_duration,
+	Error,
+};
+use crossterm::{
+	execute,
+	terminal::{EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+	backend::CrosstermBackend,
+	layout::{Constraint, Layout},
+	style::{Color, Style, Stylize},
+	widgets::{Block, Gauge, List, ListItem, ListState},
+	Frame, Terminal,
+};
+use std::{
+	io::{stdout, Stdout},
+	path::PathBuf,
+	time::Duration,
+};
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// The terminal, switched to its alternate screen.
+///
+/// The original screen is restored once the structure is dropped.
+pub struct Tui {
+	terminal: Terminal<CrosstermBackend<Stdout>>,
+	/// The [`Track`] that was last drawn.
+	///
+	/// Everything is redrawn when it changes, since other output may have been printed over the interface in between.
+	drawn: Option<PathBuf>,
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+impl Tui {
+	/// Switch to the alternate screen.
+	pub fn try_new() -> Result<Self, Error> {
+		execute!(stdout(), EnterAlternateScreen)?;
+		Ok(Self {
+			terminal: Terminal::new(CrosstermBackend::new(stdout()))?,
+			drawn: None,
+		})
+	}
+
+	/// Draw the state of a [`Playhandle`], with the given playing [`Track`] and its length.
+	pub fn draw(&mut self, handle: &Playhandle, track: &Track, length: Option<Duration>) -> Result<(), Error> {
+		if self
+			.drawn
+			.as_deref()
+			!= Some(track.file_path_get())
+		{
+			self.terminal
+				.clear()?;
+			self.drawn = Some(PathBuf::from(track.file_path_get()));
+		}
+		self.terminal
+			.draw(|frame| render(frame, handle, track, length))?;
+		Ok(())
+	}
+}
+
+impl Drop for Tui {
+	fn drop(&mut self) {
+		let _ = execute!(stdout(), LeaveAlternateScreen);
+	}
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+/// Lay the whole interface out.
+fn render(frame: &mut Frame, handle: &Playhandle, track: &Track, length: Option<Duration>) {
+	let [lists, progress, volume] = Layout::vertical([Constraint::Min(3), Constraint::Length(3), Constraint::Length(3)]).areas(frame.area());
+	let [playlists_area, tracks_area] = Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)]).areas(lists);
+
+	let current_playlist = unsafe { handle.playlist_index_get_unchecked() };
+	let current_position = unsafe { handle.track_index_get_unchecked() };
+	let selection = handle.selection_get();
+	let shown = selection.map_or(current_playlist, |(playlist_index, _)| playlist_index);
+	let playlists = handle.playlists_get();
+
+	let items: Vec<ListItem> = playlists
+		.iter()
+		.enumerate()
+		.map(|(index, playlist)| {
+			let radio = if playlist.radio_is() { ", radio" } else { "" };
+			let item = ListItem::new(format!("{:>3}  {} tracks{radio}", index + 1, playlist.tracks_count()));
+			if index == current_playlist {
+				item.bold()
+			} else {
+				item
+			}
+		})
+		.collect();
+	let list = List::new(items)
+		.block(Block::bordered().title(" Playlists "))
+		.highlight_style(Style::new().reversed());
+	frame.render_stateful_widget(list, playlists_area, &mut ListState::default().with_selected(Some(shown)));
+
+	if let Some(playlist) = playlists.get(shown) {
+		let items: Vec<ListItem> = (0..playlist.tracks_count())
+			.filter_map(|position| playlist.nth(position))
+			.enumerate()
+			.map(|(position, other)| {
+				let item = ListItem::new(format!("{:>4}  {}", position + 1, other.name_get()));
+				if shown == current_playlist && position == current_position {
+					item.bold()
+						.fg(Color::Green)
+				} else {
+					item
+				}
+			})
+			.collect();
+		// NOTE: without a selection, the playing track is highlighted invisibly, so that the list still scrolls along.
+		let highlighted = match selection {
+			Some((_, track_index)) => playlist.position_get(track_index),
+			None => (shown == current_playlist).then_some(current_position),
+		};
+		let entry = handle.entry_line_get();
+		let title = if entry.is_empty() { String::from(" Tracks ") } else { format!(" Tracks, go to: {entry} ") };
+		let list = List::new(items)
+			.block(Block::bordered().title(title))
+			.highlight_symbol("> ")
+			.highlight_style(match selection {
+				Some(_) => Style::new().reversed(),
+				None => Style::new(),
+			});
+		frame.render_stateful_widget(list, tracks_area, &mut ListState::default().with_selected(highlighted));
+	}
+
+	let position = handle.playback_position_get();
+	let ratio = length
+		.filter(|length| !length.is_zero())
+		.map_or(0.0, |length| (position.as_secs_f64() / length.as_secs_f64()).clamp(0.0, 1.0));
+	let state = if handle.playback_is_paused() { "paused" } else { "playing" };
+	let label = match length {
+		Some(length) => format!("{} / {}", fmt_duration(position), fmt_duration(length)),
+		None => fmt_duration(position),
+	};
+	let gauge = Gauge::default()
+		.block(Block::bordered().title(format!(" {state}: {} ", track.name_get())))
+		.gauge_style(Style::new().fg(Color::Green))
+		.ratio(ratio)
+		.label(label);
+	frame.render_widget(gauge, progress);
+
+	let gauge = Gauge::default()
+		.block(Block::bordered().title(" Volume "))
+		.gauge_style(Style::new().fg(Color::Blue))
+		.ratio(f64::from(handle.volume_get() / 2.0))
+		.label(format!("{:.0}%", handle.volume_get() * 100.0));
+	frame.render_widget(gauge, volume);
+}
@@ -6,8 +6,10 @@ use crossterm::{
 };
 use std::{
 	env::var,
-	io::stdout,
+	fs::create_dir_all,
+	io::{stdout, Write},
 	path::{PathBuf, MAIN_SEPARATOR_STR},
+	time::Duration,
 };
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 /// Format a text representation of a path into an absolute path.
@@ -36,20 +38,77 @@ pub fn fmt_path(path: impl AsRef<str>) -> Result<PathBuf, Error> {
 	}
 
 	let path = path.as_ref();
-	Ok(PathBuf::from(
-		path.split(MAIN_SEPARATOR_STR)
-			.enumerate()
-			.map(
-				|(index, part)| match part {
-					"~" if index == 0 => expand("HOME"),
-					_ if part.starts_with('$') => expand(&part[1..]),
-					_ => Ok(String::from(part)),
-				}, //log!(part; "expanding [{}] to a path" why; None)
-			)
-			.collect::<Result<Vec<String>, Error>>()?
-			.join(MAIN_SEPARATOR_STR),
-	)
-	.canonicalize()?)
+	let expanded = path
+		.split(MAIN_SEPARATOR_STR)
+		.enumerate()
+		.map(|(index, part)| match part {
+			"~" if index == 0 => expand("HOME"),
+			_ if part.starts_with('$') => expand(&part[1..]),
+			_ => Ok(String::from(part)),
+		})
+		.collect::<Result<Vec<String>, Error>>()
+		.inspect_err(|why| log!(Warn; "expanding '{path}' to a path; {why}"))?
+		.join(MAIN_SEPARATOR_STR);
+	match PathBuf::from(&expanded).canonicalize() {
+		Ok(canonical) => {
+			log!(Debug; "expanded '{path}' to '{}'", canonical.display());
+			Ok(canonical)
+		},
+		Err(why) => {
+			// NOTE: missing files are often expected (e.g.: when looking up the library), so the caller decides how bad it is.
+			log!(Debug; "expanding '{path}' to a path, '{expanded}' cannot be found; '{why}'");
+			Err(why)?
+		},
+	}
+}
+
+/// Get the path of a file inside of the program's state directory.
+///
+/// The directory is `$XDG_STATE_HOME/quing`, or `~/.local/state/quing` if the variable is not set.\
+/// It is created if it does not exist yet.
+pub fn state_path(name: &str) -> Result<PathBuf, Error> {
+	let directory = match var("XDG_STATE_HOME") {
+		Ok(home) if !home.is_empty() => PathBuf::from(home),
+		_ => PathBuf::from(var("HOME")?)
+			.join(".local")
+			.join("state"),
+	}
+	.join(env!("CARGO_PKG_NAME"));
+	create_dir_all(&directory)?;
+	Ok(directory.join(name))
+}
+
+/// Get the path of a file inside of the program's configuration directory.
+///
+/// The directory is `$XDG_CONFIG_HOME/quing`, or `~/.config/quing` if the variable is not set.\
+/// Unlike the other directories, it is never created.
+pub fn config_path(name: &str) -> Result<PathBuf, Error> {
+	let directory = match var("XDG_CONFIG_HOME") {
+		Ok(home) if !home.is_empty() => PathBuf::from(home),
+		_ => PathBuf::from(var("HOME")?).join(".config"),
+	};
+	Ok(directory
+		.join(env!("CARGO_PKG_NAME"))
+		.join(name))
+}
+
+/// Get the path of a file inside of the program's runtime directory.
+///
+/// The directory is `$XDG_RUNTIME_DIR/quing`, or the state directory (see [`state_path`]) if the variable is not set.\
+/// It is created if it does not exist yet.
+pub fn runtime_path(name: &str) -> Result<PathBuf, Error> {
+	let directory = match var("XDG_RUNTIME_DIR") {
+		Ok(runtime) if !runtime.is_empty() => PathBuf::from(runtime).join(env!("CARGO_PKG_NAME")),
+		_ => return state_path(name),
+	};
+	create_dir_all(&directory)?;
+	Ok(directory.join(name))
+}
+
+/// Format a [`Duration`] as minutes and seconds (e.g.: `3:07`).
+pub fn fmt_duration(duration: Duration) -> String {
+	let seconds = duration.as_secs();
+	format!("{}:{:02}", seconds / 60, seconds % 60)
 }
 
 /// Print the clear line sequence.
@@ -58,3 +117,85 @@ pub fn clear() -> Result<(), Error> {
 	print!("\r");
 	Ok(())
 }
+
+/// Replace the current line with a status line.
+pub fn status_print(line: &str) -> Result<(), Error> {
+	clear()?;
+	print!("{line}");
+	stdout()
+		.flush()
+		.map_err(Error::Io)
+}
+
+/// Score how well a text fuzzily matches a query, ignoring case.
+///
+/// Every character of the query has to appear in the text, in order; characters that follow each other directly, or start a word, score higher.\
+/// Returns [`None`] if the text does not match.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+	let mut wanted = query
+		.chars()
+		.flat_map(char::to_lowercase)
+		.filter(|character| !character.is_whitespace())
+		.peekable();
+	let mut score = 0;
+	let mut previous: Option<char> = None;
+	let mut was_matched = false;
+	for character in text
+		.chars()
+		.flat_map(char::to_lowercase)
+	{
+		let Some(&next) = wanted.peek() else { break };
+		let is_matched = character == next;
+		if is_matched {
+			wanted.next();
+			score += 1;
+			if was_matched {
+				score += 2
+			}
+			if previous.is_none_or(|previous| !previous.is_alphanumeric()) {
+				score += 3
+			}
+		}
+		was_matched = is_matched;
+		previous = Some(character);
+	}
+	wanted
+		.peek()
+		.is_none()
+		.then_some(score)
+}
+///////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn fuzzy_matches() {
+		assert!(fuzzy_score("bhr", "Bohemian Rhapsody").is_some());
+		assert!(fuzzy_score("queen", "Queen - Bohemian Rhapsody").is_some());
+		assert!(fuzzy_score("", "anything").is_some());
+		assert!(fuzzy_score("bo rh", "Bohemian Rhapsody").is_some());
+	}
+
+	#[test]
+	fn fuzzy_non_matches() {
+		assert_eq!(fuzzy_score("xyz", "Bohemian Rhapsody"), None);
+		// NOTE: the characters have to appear in order.
+		assert_eq!(fuzzy_score("rb", "Bohemian Rhapsody"), None);
+		assert_eq!(fuzzy_score("songs", "song"), None);
+	}
+
+	#[test]
+	fn fuzzy_ordering() {
+		let score = |text| fuzzy_score("rap", text).unwrap();
+		// NOTE: a run of characters scores higher than scattered ones, and a word start higher than a word's middle.
+		assert!(score("scrap metal") > score("drag up"));
+		assert!(score("rap music") > score("scrap metal"));
+	}
+
+	#[test]
+	fn fuzzy_ignores_case() {
+		assert_eq!(fuzzy_score("ROCK", "rock"), fuzzy_score("rock", "rock"));
+		assert_eq!(fuzzy_score("rock", "ROCK"), fuzzy_score("rock", "rock"));
+	}
+}